@@ -1,8 +1,6 @@
 use std::fs::File;
 use std::io::{
     self,
-    BufRead,
-    BufReader,
     IsTerminal,
     Read,
     Seek,
@@ -15,13 +13,54 @@ use std::{
     fs,
 };
 
-use biip::Biip;
+use biip::stream::StreamRedactor;
+use biip::style::Style;
+use biip::{
+    Biip,
+    Config,
+    ConfigError,
+    Mapping,
+    Severity,
+};
 use dotenv::dotenv;
 
 const HELP: &str = r#"Usage:
   cat file | biip
   biip [FILE ...]   # read and redact one or more files
   biip              # open default editor for interactive input.
+  biip unredact --map FILE [FILE ...]
+                    # restore the values of the placeholders in the files (or
+                    # stdin), from a mapping saved with --map
+
+Options:
+  --profile NAME    # use a profile from the configuration file
+  --style STYLE     # how redactions are shown: bullets, ascii, labels,
+                    # numbered, pseudonyms, mask:C, or preserve-length[:C]
+  --key-file PATH   # key for the pseudonyms style, so that tokens are the
+                    # same across runs, and to encrypt the mapping
+  --min-severity LEVEL
+                    # only redact values at least this sensitive:
+                    # quasi_identifier, identifier, or secret
+  --aggressive      # also redact values which are usually not sensitive,
+                    # e.g. private and link-local IP addresses
+  --keep-oui        # keep the vendor part (OUI) of MAC addresses, e.g.
+                    # 00:1A:2B:••:••:••
+  --keep-ipv4-prefix BITS
+                    # keep the subnet (8, 16 or 24 leading bits) of public
+                    # IPv4 addresses, e.g. 203.0.113.•• for 24
+  --map FILE        # save a mapping of placeholders to the values they
+                    # replaced (numbered style, unless pseudonyms), as JSON
+                    # or encrypted with --key-file
+
+  --show-config     # print the effective configuration, and exit
+
+Environment:
+  BIIP_DISABLE      # redactors to disable, e.g. BIIP_DISABLE=uuid,ipv6
+  BIIP_STYLE        # like --style
+  BIIP_MIN_SEVERITY # like --min-severity
+
+Flags take precedence over the environment, which takes precedence over the
+configuration file (~/.config/biip/config.toml).
 "#;
 
 fn main() -> io::Result<()> {
@@ -30,8 +69,7 @@ fn main() -> io::Result<()> {
     let stdin = io::stdin();
     let mut stdout = io::stdout();
     let mut stderr = io::stderr();
-    let biip = Biip::new();
-    let args: Vec<String> = env::args().skip(1).collect();
+    let mut args: Vec<String> = env::args().skip(1).collect();
 
     // Help
     if args.iter().any(|a| a == "-h" || a == "--help") {
@@ -39,34 +77,314 @@ fn main() -> io::Result<()> {
         return Ok(());
     }
 
-    // If file args are provided, read each in order.
-    if !args.is_empty() {
-        run_with_args(&args, &biip, &mut stdout, &mut stderr)?;
+    if args.first().is_some_and(|a| a == "unredact") {
+        args.remove(0);
+        if let Err(err) = run_unredact(&mut args, &stdin, &mut stdout) {
+            writeln!(stderr, "biip: {}", err)?;
+            std::process::exit(2);
+        }
         return Ok(());
     }
 
-    // If input is piped, read from stdin.
-    if !stdin.is_terminal() {
-        run_with_piped_stdin(&stdin, &biip, &mut stdout)?;
+    let options = match parse_options(&mut args) {
+        Ok(options) => options,
+        Err(err) => {
+            writeln!(stderr, "biip: {}", err)?;
+            std::process::exit(2);
+        }
+    };
+    if options.show_config {
+        write!(stdout, "{}", options.config.to_toml())?;
         return Ok(());
     }
+    let biip = match build_biip(&options) {
+        Ok(biip) => biip,
+        Err(err) => {
+            writeln!(stderr, "biip: {}", err)?;
+            std::process::exit(2);
+        }
+    };
+
+    if !args.is_empty() {
+        // If file args are provided, read each in order.
+        run_with_args(&args, &biip, &mut stdout, &mut stderr)?;
+    } else if !stdin.is_terminal() {
+        // If input is piped, read from stdin.
+        run_with_piped_stdin(&stdin, &biip, &mut stdout)?;
+    } else {
+        // Interactive editor mode.
+        let editor = find_editor();
+        run_with_editor(&editor, &biip, &mut stdout, &mut stderr)?;
+    }
 
-    // Interactive editor mode.
-    let editor = find_editor();
-    run_with_editor(&editor, &biip, &mut stdout, &mut stderr)
+    match (options.map, biip.mapping()) {
+        (Some(map), Some(mapping)) => map.save(&mapping),
+        _ => Ok(()),
+    }
 }
 
-fn process_lines<R: BufRead>(
-    reader: R,
-    biip: &Biip,
+// Where to save the mapping of placeholders to the values they replaced.
+struct MapFile {
+    path: String,
+    // The key to encrypt the mapping with, if any.
+    key: Option<Vec<u8>>,
+}
+
+impl MapFile {
+    fn save(&self, mapping: &Mapping) -> io::Result<()> {
+        let data = match &self.key {
+            Some(key) => mapping.encrypt(key),
+            None => mapping.to_json().into_bytes(),
+        };
+        fs::write(&self.path, data)
+    }
+
+    fn load(&self) -> Result<Mapping, String> {
+        let data = fs::read(&self.path).map_err(|err| {
+            format!("cannot read mapping '{}': {}", self.path, err)
+        })?;
+        let mapping = match &self.key {
+            Some(key) => Mapping::decrypt(&data, key),
+            None => Mapping::from_json(&String::from_utf8_lossy(&data)),
+        };
+        mapping.map_err(|err| format!("{} ('{}')", err, self.path))
+    }
+}
+
+// Restores the values replaced in the files given (or stdin), from the
+// mapping given with `--map`.
+fn run_unredact(
+    args: &mut Vec<String>,
+    stdin: &io::Stdin,
     out: &mut dyn Write,
-) -> io::Result<()> {
-    for line_res in reader.lines() {
-        writeln!(out, "{}", biip.process(&line_res?))?;
+) -> Result<(), String> {
+    let path = take_flag_value(args, "--map")?
+        .ok_or("unredact needs a mapping, with --map FILE")?;
+    let key = take_flag_value(args, "--key-file")?
+        .map(|path| read_key(&path))
+        .transpose()?;
+    let mapping = MapFile { path, key }.load()?;
+
+    let mut texts = Vec::new();
+    if args.is_empty() {
+        let mut text = String::new();
+        stdin
+            .lock()
+            .read_to_string(&mut text)
+            .map_err(|err| err.to_string())?;
+        texts.push(text);
+    }
+    for path in args.iter() {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("cannot read '{}': {}", path, err))?;
+        texts.push(text);
+    }
+    for text in texts {
+        out.write_all(mapping.unredact(&text).as_bytes())
+            .map_err(|err| err.to_string())?;
     }
     Ok(())
 }
 
+// The options given on the command line.
+struct Options {
+    // The effective configuration (see `effective_config`).
+    config: Config,
+    // The key for pseudonyms and the mapping.
+    key: Option<Vec<u8>>,
+    map: Option<MapFile>,
+    show_config: bool,
+}
+
+// The flags overriding the configuration.
+#[derive(Default)]
+struct Flags {
+    style: Option<Style>,
+    min_severity: Option<Severity>,
+    aggressive: bool,
+    keep_oui: bool,
+    keep_ipv4_prefix: Option<u8>,
+}
+
+// Takes the options out of the arguments, and resolves the configuration
+// they select.
+fn parse_options(args: &mut Vec<String>) -> Result<Options, String> {
+    let profile = take_flag_value(args, "--profile")?;
+    let show_config = take_flag(args, "--show-config");
+    let flags = Flags {
+        style: take_flag_value(args, "--style")?
+            .map(|style| style.parse::<Style>())
+            .transpose()?,
+        min_severity: take_flag_value(args, "--min-severity")?
+            .map(|severity| severity.parse::<Severity>())
+            .transpose()?,
+        aggressive: take_flag(args, "--aggressive"),
+        keep_oui: take_flag(args, "--keep-oui"),
+        keep_ipv4_prefix: take_flag_value(args, "--keep-ipv4-prefix")?
+            .map(|bits| parse_prefix(&bits))
+            .transpose()?,
+    };
+    let key = take_flag_value(args, "--key-file")?
+        .map(|path| read_key(&path))
+        .transpose()?;
+    let map = take_flag_value(args, "--map")?.map(|path| MapFile {
+        path,
+        key: key.clone(),
+    });
+
+    let mut config = effective_config(profile.as_deref(), &flags)
+        .map_err(|err| err.to_string())?;
+    if map.is_some() {
+        // Only numbered and pseudonymous placeholders identify values.
+        match (config.style, flags.style) {
+            (Some(Style::Numbered | Style::Pseudonyms), _) => {}
+            (_, None) => config.style = Some(Style::Numbered),
+            (_, Some(style)) => {
+                return Err(format!(
+                    "--map needs the numbered or pseudonyms style, not '{}'",
+                    style
+                ));
+            }
+        }
+    }
+    Ok(Options {
+        config,
+        key,
+        map,
+        show_config,
+    })
+}
+
+// Reads a key from a file, ignoring any trailing whitespace (e.g. the final
+// newline).
+fn read_key(path: &str) -> Result<Vec<u8>, String> {
+    let key = fs::read(path)
+        .map_err(|err| format!("cannot read key file '{}': {}", path, err))?;
+    Ok(key.trim_ascii_end().to_vec())
+}
+
+// Resolves the effective configuration, from (in increasing precedence) the
+// configuration file with the selected profile, the `BIIP_*` environment
+// variables, and the flags.
+fn effective_config(
+    profile: Option<&str>,
+    flags: &Flags,
+) -> Result<Config, ConfigError> {
+    let mut config = Config::load_default()?.unwrap_or_default();
+    if let Some(profile) = profile {
+        config = config.with_profile(profile)?;
+    }
+    // Profiles have been applied, so only clutter `--show-config`.
+    config.profiles.clear();
+    Ok(with_flags(config.with_env()?, flags))
+}
+
+// Overrides the configuration with the flags.
+fn with_flags(mut config: Config, flags: &Flags) -> Config {
+    if let Some(style) = flags.style {
+        config.style = Some(style);
+    }
+    if let Some(severity) = flags.min_severity {
+        config.min_severity = Some(severity);
+    }
+    config.aggressive |= flags.aggressive;
+    if flags.keep_oui {
+        config
+            .options
+            .entry("mac_address".to_string())
+            .or_default()
+            .keep_oui = true;
+    }
+    if let Some(bits) = flags.keep_ipv4_prefix {
+        config
+            .options
+            .entry("ipv4".to_string())
+            .or_default()
+            .keep_prefix = Some(bits);
+    }
+    config
+}
+
+// Parses the number of leading bits of IPv4 addresses to keep, which must be
+// whole octets (but not all of them).
+fn parse_prefix(bits: &str) -> Result<u8, String> {
+    match bits.parse::<u8>() {
+        Ok(bits @ (8 | 16 | 24)) => Ok(bits),
+        _ => Err(format!(
+            "invalid IPv4 prefix length '{}' (expected 8, 16 or 24)",
+            bits
+        )),
+    }
+}
+
+// Builds the `Biip` instance with the default redactors, customized by the
+// options.
+fn build_biip(options: &Options) -> Result<Biip, ConfigError> {
+    let mut builder = options.config.apply(Biip::builder())?;
+    if let Some(key) = &options.key {
+        builder = builder.with_key(key);
+    }
+    if options.map.is_some() {
+        builder = builder.with_mapping();
+    }
+    Ok(builder.build())
+}
+
+// Removes a `--flag` option from the arguments, returning whether it was
+// there.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|a| a != flag);
+    args.len() != len
+}
+
+// Removes a `--flag VALUE` (or `--flag=VALUE`) option from the arguments,
+// returning its value.
+fn take_flag_value(
+    args: &mut Vec<String>,
+    flag: &str,
+) -> Result<Option<String>, String> {
+    let prefix = format!("{}=", flag);
+    let Some(index) =
+        args.iter().position(|a| a == flag || a.starts_with(&prefix))
+    else {
+        return Ok(None);
+    };
+    let arg = args.remove(index);
+    if let Some(value) = arg.strip_prefix(&prefix) {
+        return Ok(Some(value.to_string()));
+    }
+    if index < args.len() {
+        Ok(Some(args.remove(index)))
+    } else {
+        Err(format!("missing value for {}", flag))
+    }
+}
+
+// Redacts `reader` into `out` through `stream`. Complete lines are written as
+// soon as they are read, except those within the stream's window, or part of
+// a (multi-line) value which hasn't ended yet.
+fn process_stream<R: Read>(
+    mut reader: R,
+    mut stream: StreamRedactor,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut buf = [0u8; 8192];
+    let mut redacted = String::new();
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        redacted.clear();
+        stream.push_bytes(&buf[..n], &mut redacted);
+        out.write_all(redacted.as_bytes())?;
+    }
+    redacted.clear();
+    stream.finish(&mut redacted);
+    out.write_all(redacted.as_bytes())
+}
+
 fn run_with_args(
     paths: &[String],
     biip: &Biip,
@@ -98,8 +416,7 @@ fn process_file_path(
     if show_header {
         writeln!(out, "─── {} ───", path)?;
     }
-    let reader = BufReader::new(file);
-    process_lines(reader, biip, out)
+    process_stream(file, StreamRedactor::new(biip.clone()), out)
 }
 
 fn run_with_piped_stdin(
@@ -107,7 +424,10 @@ fn run_with_piped_stdin(
     biip: &Biip,
     out: &mut dyn Write,
 ) -> io::Result<()> {
-    process_lines(stdin.lock(), biip, out)
+    // Piped input may be interactive (e.g. `tail -f`), so hold back only
+    // what is still unterminated.
+    let stream = StreamRedactor::with_window(biip.clone(), 0);
+    process_stream(stdin.lock(), stream, out)
 }
 
 fn find_editor() -> String {
@@ -134,7 +454,7 @@ fn run_with_editor(
         .ok();
 
     // Launch the editor process and wait for it to exit.
-    let mut cmd = Command::new(&editor);
+    let mut cmd = Command::new(editor);
     cmd.arg(&temp_path);
 
     // If we successfully opened /dev/tty, use it for stdin/stdout/stderr
@@ -155,9 +475,8 @@ fn run_with_editor(
 
     match status {
         Ok(status) if status.success() => {
-            let file = File::open(&temp_path)?;
-            let reader = BufReader::new(file);
-            process_lines(reader, biip, out)
+            let stream = StreamRedactor::new(biip.clone());
+            process_stream(File::open(&temp_path)?, stream, out)
         }
         Ok(_) => {
             writeln!(err, "Editor closed without saving. Aborting.")?;
@@ -193,7 +512,7 @@ fn is_probably_binary(file: &mut File) -> io::Result<bool> {
         return Ok(false);
     }
     // If NUL byte present, very likely binary (matches less/grep heuristics)
-    if slice.iter().any(|&b| b == 0) {
+    if slice.contains(&0) {
         return Ok(true);
     }
     // If not valid UTF-8, treat as binary to avoid mojibake
@@ -236,7 +555,7 @@ mod tests {
         let mut out = Vec::new();
         let mut err = Vec::new();
         run_with_args(
-            &vec![text_p.to_string_lossy().into()],
+            &[text_p.to_string_lossy().into()],
             &biip,
             &mut out,
             &mut err,
@@ -248,16 +567,130 @@ mod tests {
     }
 
     #[test]
-    fn test_process_lines_redacts_email() {
-        let biip = Biip::new();
+    fn test_take_flag_value() {
+        let mut args: Vec<String> =
+            ["a", "--profile", "work", "b"].map(String::from).to_vec();
+        let profile = take_flag_value(&mut args, "--profile").unwrap();
+        assert_eq!(profile.as_deref(), Some("work"));
+        assert_eq!(args, ["a", "b"]);
+
+        let mut args = vec!["--profile=home".to_string()];
+        let profile = take_flag_value(&mut args, "--profile").unwrap();
+        assert_eq!(profile.as_deref(), Some("home"));
+        assert!(args.is_empty());
+
+        let mut args = vec!["--profile".to_string()];
+        assert!(take_flag_value(&mut args, "--profile").is_err());
+        assert_eq!(take_flag_value(&mut args, "--profile"), Ok(None));
+
+        let mut args = ["a", "--aggressive"].map(String::from).to_vec();
+        assert!(take_flag(&mut args, "--aggressive"));
+        assert!(!take_flag(&mut args, "--aggressive"));
+        assert_eq!(args, ["a"]);
+    }
+
+    #[test]
+    fn test_with_flags() {
+        let config = Config {
+            style: Some(Style::Labels),
+            min_severity: Some(Severity::Secret),
+            ..Default::default()
+        };
+        let flags = Flags {
+            style: Some(Style::Ascii),
+            ..Default::default()
+        };
+        let config = with_flags(config, &flags);
+        assert_eq!(config.style, Some(Style::Ascii));
+        assert_eq!(config.min_severity, Some(Severity::Secret));
+        assert!(!config.aggressive);
+        assert!(config.options.is_empty());
+
+        let flags = Flags {
+            keep_oui: true,
+            keep_ipv4_prefix: Some(24),
+            ..Default::default()
+        };
+        let config = with_flags(config, &flags);
+        assert!(config.options["mac_address"].keep_oui);
+        assert_eq!(config.options["ipv4"].keep_prefix, Some(24));
+
+        assert_eq!(parse_prefix("16"), Ok(16));
+        assert!(parse_prefix("33").is_err());
+        assert!(parse_prefix("/24").is_err());
+        assert!(parse_prefix("32").is_err());
+
+        let mut args = vec!["--keep-ipv4-prefix".to_string(), "20".to_string()];
+        let Err(err) = parse_options(&mut args) else {
+            panic!("expected an invalid prefix length error");
+        };
+        assert!(err.contains("'20'"));
+    }
+
+    #[test]
+    fn test_map_file_round_trip() {
+        let mut mapping = Mapping::new();
+        mapping.insert("[EMAIL-1]", "a@b.io");
+        for key in [None, Some(b"key".to_vec())] {
+            let path = tmp_file_with(b"", "map");
+            let map = MapFile {
+                path: path.to_string_lossy().into_owned(),
+                key,
+            };
+            map.save(&mapping).unwrap();
+            assert_eq!(map.load().unwrap(), mapping);
+            fs::remove_file(path).ok();
+        }
+
+        let path = tmp_file_with(b"{}", "map_encrypted");
+        let map = MapFile {
+            path: path.to_string_lossy().into_owned(),
+            key: Some(b"key".to_vec()),
+        };
+        assert!(map.load().is_err());
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_process_stream_redacts_email() {
+        let stream = StreamRedactor::with_window(Biip::new(), 0);
         let input = b"email: foo@bar.com\n";
         let reader = Cursor::new(&input[..]);
         let mut out = Vec::new();
-        process_lines(reader, &biip, &mut out).unwrap();
+        process_stream(reader, stream, &mut out).unwrap();
         let s = String::from_utf8(out).unwrap();
         assert!(s.contains("•••@•••"));
     }
 
+    #[test]
+    fn test_process_stream_redacts_across_lines() {
+        let biip = Biip::builder()
+            .without_defaults()
+            .with_custom(biip::Redactor::regex(
+                regex::Regex::new(r"BEGIN\s+SECRET").unwrap(),
+                None,
+            ))
+            .build();
+        let input = b"BEGIN\nSECRET\nfoo\n";
+        let mut out = Vec::new();
+        let stream = StreamRedactor::new(biip);
+        process_stream(&input[..], stream, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "•••\nfoo\n");
+    }
+
+    #[test]
+    fn test_process_stream_redacts_blocks() {
+        let biip = Biip::builder()
+            .without_defaults()
+            .with(biip::Redactor::block("BEGIN", "END", None).ok())
+            .build();
+        let input = b"a\nBEGIN\nsecret\nEND\nb\n";
+        let mut out = Vec::new();
+        let stream = StreamRedactor::with_window(biip, 0);
+        process_stream(&input[..], stream, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "a\n•••\nb\n");
+    }
+
     #[test]
     fn test_run_with_args_skips_binary_and_prints_header_for_text() {
         let text_p = tmp_file_with(b"hello user foo@bar.com", "text2");
@@ -266,7 +699,7 @@ mod tests {
         let mut out = Vec::new();
         let mut err = Vec::new();
         run_with_args(
-            &vec![
+            &[
                 text_p.to_string_lossy().into(),
                 bin_p.to_string_lossy().into(),
             ],
@@ -0,0 +1,577 @@
+//! Configuration files.
+//!
+//! `biip` reads its configuration from `$XDG_CONFIG_HOME/biip/config.toml`,
+//! falling back to `~/.config/biip/config.toml`. All sections are optional:
+//!
+//! ```toml
+//! # How redacted values are shown: "bullets" (the default), "ascii", or
+//! # { mask = "#" }.
+//! style = "ascii"
+//!
+//! # Only redact values at least this sensitive: "quasi_identifier" (e.g. IP
+//! # addresses, the default), "identifier" (e.g. emails), or "secret" (e.g.
+//! # API keys).
+//! min_severity = "identifier"
+//!
+//! # Built-in redactors to turn off, by name.
+//! disabled = ["uuid"]
+//!
+//! # Opt-in built-in redactors to turn on, by name.
+//! enabled = ["certificates"]
+//!
+//! # Regions to turn on the regional built-in redactors of, e.g. "in" for
+//! # Indian Aadhaar and PAN numbers.
+//! regions = ["in"]
+//!
+//! # Built-in redactors which only mask part of each value, e.g. keeping the
+//! # domain of emails.
+//! partial = ["email"]
+//!
+//! # Built-in redactors which pseudonymize values preserving their prefixes,
+//! # e.g. so that IP addresses in the same subnet stay in the same subnet.
+//! prefix_preserving = ["ipv4", "ipv6"]
+//!
+//! # Also redact values which are usually not sensitive, e.g. private IPs.
+//! aggressive = true
+//!
+//! # Values which are never redacted.
+//! allowlist = ["noreply@example.com"]
+//!
+//! # Options for the built-in redactors, by name.
+//! [options.email]
+//! keep_domain = true
+//!
+//! [options.ipv4]
+//! keep_prefix = 24
+//!
+//! [options.mac_address]
+//! keep_oui = true
+//!
+//! # Replacements to use instead of the defaults, by redactor name.
+//! [replacements]
+//! email = "[email]"
+//!
+//! # Custom patterns to redact.
+//! [[patterns]]
+//! name = "ticket"
+//! pattern = 'ACME-\d+'
+//! replacement = "[ticket]"
+//! case_insensitive = true
+//!
+//! # Profiles, with the same sections, extend the above when selected (e.g.
+//! # with `biip --profile work`).
+//! [profiles.work]
+//! disabled = ["ipv4"]
+//!
+//! [[profiles.work.patterns]]
+//! name = "hostname"
+//! pattern = '\b[a-z0-9-]+\.corp\.example\.com\b'
+//! ```
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::{
+    env,
+    fs,
+    io,
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::biip::BiipBuilder;
+use crate::error::ConfigError;
+use crate::redactor::Severity;
+use crate::redactors::env::{
+    DISABLE_VAR,
+    MIN_SEVERITY_VAR,
+    STYLE_VAR,
+    parse_names,
+};
+use crate::redactors::{
+    RedactorOptions,
+    regional_names,
+};
+use crate::spec::RedactorSpec;
+use crate::style::Style;
+
+/// The configuration for a [`Biip`](crate::Biip) instance.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The style of the replacements.
+    pub style: Option<Style>,
+    /// The minimum severity of the values to redact.
+    pub min_severity: Option<Severity>,
+    /// The names of the built-in redactors to disable.
+    pub disabled: Vec<String>,
+    /// The names of the opt-in built-in redactors to enable.
+    pub enabled: Vec<String>,
+    /// The regions (e.g. `in`) to enable the regional built-in redactors of.
+    pub regions: Vec<String>,
+    /// The names of the built-in redactors to only partially mask values.
+    pub partial: Vec<String>,
+    /// The names of the built-in redactors to pseudonymize values, preserving
+    /// their prefixes.
+    pub prefix_preserving: Vec<String>,
+    /// Whether to also redact values which are usually not sensitive, e.g.
+    /// private IP addresses.
+    pub aggressive: bool,
+    /// Options for the built-in redactors, by name.
+    pub options: BTreeMap<String, RedactorOptions>,
+    /// Values which are never redacted.
+    pub allowlist: Vec<String>,
+    /// Replacements to use instead of the defaults, by redactor name.
+    pub replacements: BTreeMap<String, String>,
+    /// Custom patterns to redact, applied after the built-in redactors.
+    pub patterns: Vec<RedactorSpec>,
+    /// Named profiles, which extend the configuration when selected.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// A named set of additions to a [`Config`], e.g. for `work`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// The minimum severity of the values to redact, instead of the one in
+    /// the configuration.
+    pub min_severity: Option<Severity>,
+    /// The names of further built-in redactors to disable.
+    pub disabled: Vec<String>,
+    /// The names of further opt-in built-in redactors to enable.
+    pub enabled: Vec<String>,
+    /// Further regions to enable the regional built-in redactors of.
+    pub regions: Vec<String>,
+    /// The names of further built-in redactors to only partially mask values.
+    pub partial: Vec<String>,
+    /// The names of further built-in redactors to pseudonymize values,
+    /// preserving their prefixes.
+    pub prefix_preserving: Vec<String>,
+    /// Whether to also redact values which are usually not sensitive.
+    pub aggressive: bool,
+    /// Further options for the built-in redactors, by name. These take
+    /// precedence over the ones in the configuration.
+    pub options: BTreeMap<String, RedactorOptions>,
+    /// Further values which are never redacted.
+    pub allowlist: Vec<String>,
+    /// Further replacement overrides, by redactor name. These take precedence
+    /// over the ones in the configuration.
+    pub replacements: BTreeMap<String, String>,
+    /// Further custom patterns to redact.
+    pub patterns: Vec<RedactorSpec>,
+}
+
+impl Config {
+    /// Parses a configuration from TOML.
+    pub fn from_toml(toml: &str) -> Result<Config, ConfigError> {
+        toml::from_str(toml)
+            .map_err(|source| ConfigError::Parse { path: None, source })
+            .and_then(Config::validated)
+    }
+
+    /// Loads the configuration from a file.
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        let toml =
+            fs::read_to_string(path).map_err(|source| ConfigError::Io {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        toml::from_str(&toml)
+            .map_err(|source| ConfigError::Parse {
+                path: Some(path.to_path_buf()),
+                source,
+            })
+            .and_then(Config::validated)
+    }
+
+    // Checks the redactor options (in the profiles too) which can't be
+    // checked while parsing: IPv4 prefixes must be whole octets (but not all
+    // of them), as with `--keep-ipv4-prefix`.
+    fn validated(self) -> Result<Config, ConfigError> {
+        let profiles = self.profiles.values().map(|profile| &profile.options);
+        for options in std::iter::once(&self.options).chain(profiles) {
+            match options.get("ipv4").and_then(|opts| opts.keep_prefix) {
+                None | Some(8 | 16 | 24) => {}
+                Some(bits) => {
+                    return Err(ConfigError::Options {
+                        name: "ipv4".to_string(),
+                        reason: format!(
+                            "invalid keep_prefix {} (expected 8, 16 or 24)",
+                            bits
+                        ),
+                    });
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Loads the configuration from the default path (see
+    /// [`Config::default_path`]). Returns `None` if there is no configuration
+    /// file.
+    pub fn load_default() -> Result<Option<Config>, ConfigError> {
+        let Some(path) = Config::default_path() else {
+            return Ok(None);
+        };
+        match Config::load(&path) {
+            Err(ConfigError::Io { source, .. })
+                if source.kind() == io::ErrorKind::NotFound =>
+            {
+                Ok(None)
+            }
+            result => result.map(Some),
+        }
+    }
+
+    /// Returns the default path of the configuration file:
+    /// `$XDG_CONFIG_HOME/biip/config.toml`, or `~/.config/biip/config.toml`
+    /// if `XDG_CONFIG_HOME` is not set.
+    pub fn default_path() -> Option<PathBuf> {
+        config_path(env::var_os("XDG_CONFIG_HOME"), env::home_dir())
+    }
+
+    /// Selects a profile, extending the configuration with it.
+    ///
+    /// Fails if there is no profile with the given name.
+    pub fn with_profile(mut self, name: &str) -> Result<Config, ConfigError> {
+        let profile = self
+            .profiles
+            .remove(name)
+            .ok_or_else(|| ConfigError::UnknownProfile(name.to_string()))?;
+        self.min_severity = profile.min_severity.or(self.min_severity);
+        self.disabled.extend(profile.disabled);
+        self.enabled.extend(profile.enabled);
+        self.regions.extend(profile.regions);
+        self.partial.extend(profile.partial);
+        self.prefix_preserving.extend(profile.prefix_preserving);
+        self.aggressive |= profile.aggressive;
+        self.options.extend(profile.options);
+        self.allowlist.extend(profile.allowlist);
+        self.replacements.extend(profile.replacements);
+        self.patterns.extend(profile.patterns);
+        Ok(self)
+    }
+
+    /// Overrides the configuration with the `BIIP_*` environment variables
+    /// which configure biip: `BIIP_STYLE` and `BIIP_MIN_SEVERITY` replace the
+    /// style and the minimum severity, and the redactors in `BIIP_DISABLE`
+    /// are disabled too.
+    ///
+    /// Fails if any of the variables has an invalid value.
+    pub fn with_env(self) -> Result<Config, ConfigError> {
+        self.with_vars(|name| env::var(name).ok())
+    }
+
+    fn with_vars<F>(mut self, var: F) -> Result<Config, ConfigError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let invalid = |var: &'static str| {
+            move |reason| ConfigError::Env {
+                var: var.to_string(),
+                reason,
+            }
+        };
+        if let Some(style) = var(STYLE_VAR) {
+            self.style = Some(style.parse().map_err(invalid(STYLE_VAR))?);
+        }
+        if let Some(severity) = var(MIN_SEVERITY_VAR) {
+            let severity =
+                severity.parse().map_err(invalid(MIN_SEVERITY_VAR))?;
+            self.min_severity = Some(severity);
+        }
+        let disabled = var(DISABLE_VAR).map(|names| parse_names(&names));
+        for name in disabled.unwrap_or_default() {
+            if !self.disabled.contains(&name) {
+                self.disabled.push(name);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Serializes the configuration as TOML, e.g. to show the effective
+    /// configuration.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("the configuration serializes to TOML")
+    }
+
+    /// Applies the configuration to a builder.
+    ///
+    /// Fails if any of the custom patterns is invalid.
+    pub fn apply(
+        &self,
+        mut builder: BiipBuilder,
+    ) -> Result<BiipBuilder, ConfigError> {
+        if let Some(style) = self.style {
+            builder = builder.with_style(style);
+        }
+        if let Some(severity) = self.min_severity {
+            builder = builder.with_min_severity(severity);
+        }
+        for name in &self.disabled {
+            builder = builder.without(name);
+        }
+        let regional = self
+            .regions
+            .iter()
+            .flat_map(|region| regional_names(region).map(String::from));
+        let enabled = self.enabled.iter().cloned().chain(regional);
+        for name in enabled.filter(|name| !self.disabled.contains(name)) {
+            builder = builder.enable(&name);
+        }
+        for (name, options) in &self.options {
+            builder = builder.with_options(name, options.clone());
+        }
+        for name in &self.partial {
+            builder = builder.with_partial(name);
+        }
+        for name in &self.prefix_preserving {
+            builder = builder.with_prefix_preserving(name);
+        }
+        if self.aggressive {
+            builder = builder.aggressive();
+        }
+        for value in &self.allowlist {
+            builder = builder.allow(value);
+        }
+        for (name, replacement) in &self.replacements {
+            builder = builder.with_replacement(name, replacement);
+        }
+        for spec in &self.patterns {
+            let redactor =
+                spec.build().map_err(|source| ConfigError::Pattern {
+                    name: spec.name.clone(),
+                    source,
+                })?;
+            builder = builder.with_named(&spec.name, Some(redactor));
+        }
+        Ok(builder)
+    }
+}
+
+fn config_path(
+    xdg_config_home: Option<OsString>,
+    home: Option<PathBuf>,
+) -> Option<PathBuf> {
+    // Per the XDG spec, relative paths are invalid and should be ignored.
+    let base = xdg_config_home
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| home.map(|home| home.join(".config")))?;
+    Some(base.join("biip").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::biip::Biip;
+    use crate::redactors;
+
+    #[test]
+    fn test_config_path() {
+        let home = Some(PathBuf::from("/home/me"));
+        assert_eq!(
+            config_path(Some("/xdg".into()), home.clone()),
+            Some(PathBuf::from("/xdg/biip/config.toml"))
+        );
+        assert_eq!(
+            config_path(Some("relative".into()), home.clone()),
+            Some(PathBuf::from("/home/me/.config/biip/config.toml"))
+        );
+        assert_eq!(
+            config_path(None, home),
+            Some(PathBuf::from("/home/me/.config/biip/config.toml"))
+        );
+        assert_eq!(config_path(None, None), None);
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let config = Config::from_toml(
+            r##"
+            style = { mask = "#" }
+            disabled = ["uuid"]
+            allowlist = ["noreply@example.com"]
+
+            [replacements]
+            email = "[email]"
+
+            [[patterns]]
+            name = "ticket"
+            pattern = 'ACME-\d+'
+            case_insensitive = true
+            "##,
+        )
+        .unwrap();
+        let builder = Biip::builder()
+            .without_defaults()
+            .with_named("email", redactors::email_redactor())
+            .with_named("uuid", redactors::uuid_redactor());
+        let biip = config.apply(builder).unwrap().build();
+        assert_eq!(
+            biip.process(
+                "a@b.io noreply@example.com acme-1 \
+                 123e4567-e89b-12d3-a456-426614174000"
+            ),
+            "[email] noreply@example.com ### \
+             123e4567-e89b-12d3-a456-426614174000"
+        );
+    }
+
+    #[test]
+    fn test_config_options() {
+        let config = Config::from_toml(
+            r#"
+            partial = ["email"]
+            enabled = ["certificates"]
+            regions = ["in"]
+            disabled = ["pan"]
+
+            [options.ipv4]
+            keep_prefix = 24
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.options["ipv4"].keep_prefix, Some(24));
+        let builder = Biip::builder()
+            .with_categories(&[redactors::Category::Network]);
+        let biip = config.apply(builder).unwrap().build();
+        assert_eq!(biip.process("a@b.io 8.8.4.4"), "•••@b.io 8.8.4.••");
+        let cert = "-----BEGIN CERTIFICATE-----\n-----END CERTIFICATE-----";
+        assert_eq!(biip.process(cert), "••••📜•");
+        assert_eq!(
+            biip.process("2341 2341 2346 ABCPE1234F"),
+            "•••• •••• •••• ABCPE1234F"
+        );
+
+        let err = Config::from_toml("[options.ipv4]\nkeep = 1").unwrap_err();
+        assert!(matches!(err, ConfigError::Parse { .. }));
+    }
+
+    #[test]
+    fn test_config_profiles() {
+        let config = Config::from_toml(
+            r#"
+            disabled = ["uuid"]
+
+            [replacements]
+            email = "[email]"
+
+            [profiles.work]
+            min_severity = "secret"
+            disabled = ["ipv4"]
+            replacements = { email = "[work-email]" }
+
+            [[profiles.work.patterns]]
+            name = "hostname"
+            pattern = '\bdb\d+\.corp\b'
+            "#,
+        )
+        .unwrap();
+
+        let work = config.clone().with_profile("work").unwrap();
+        assert_eq!(work.disabled, ["uuid", "ipv4"]);
+        assert_eq!(work.min_severity, Some(Severity::Secret));
+        assert_eq!(work.replacements["email"], "[work-email]");
+        let builder = Biip::builder()
+            .without_defaults()
+            .with_named("email", redactors::email_redactor());
+        let biip = work.apply(builder).unwrap().build();
+        assert_eq!(biip.process("a@b.io on db1.corp"), "[work-email] on •••");
+
+        let err = config.with_profile("home").unwrap_err();
+        assert_eq!(err.to_string(), "unknown profile 'home'");
+    }
+
+    #[test]
+    fn test_config_with_env() {
+        let config = Config::from_toml(
+            r#"
+            style = "labels"
+            min_severity = "identifier"
+            disabled = ["uuid"]
+            "#,
+        )
+        .unwrap();
+        let vars = |name: &str| match name {
+            "BIIP_STYLE" => Some("ascii".to_string()),
+            "BIIP_DISABLE" => Some("uuid,ipv6".to_string()),
+            _ => None,
+        };
+        let config = config.with_vars(vars).unwrap();
+        assert_eq!(config.style, Some(Style::Ascii));
+        assert_eq!(config.min_severity, Some(Severity::Identifier));
+        assert_eq!(config.disabled, ["uuid", "ipv6"]);
+
+        let invalid = |name: &str| {
+            (name == "BIIP_MIN_SEVERITY").then(|| "high".to_string())
+        };
+        let err = Config::default().with_vars(invalid).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid BIIP_MIN_SEVERITY: unknown severity 'high'"
+        );
+    }
+
+    #[test]
+    fn test_config_to_toml() {
+        let toml = r##"
+            style = { mask = "#" }
+            disabled = ["uuid"]
+
+            [options.ipv4]
+            keep_prefix = 24
+
+            [[patterns]]
+            name = "ticket"
+            pattern = 'ACME-\d+'
+        "##;
+        let config = Config::from_toml(toml).unwrap();
+        assert_eq!(Config::from_toml(&config.to_toml()).unwrap(), config);
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let err = Config::from_toml("disabld = []").unwrap_err();
+        assert!(matches!(err, ConfigError::Parse { path: None, .. }));
+
+        let config = Config::from_toml(
+            r#"
+            [[patterns]]
+            name = "broken"
+            pattern = "("
+            "#,
+        )
+        .unwrap();
+        let Err(err) = config.apply(BiipBuilder::new()) else {
+            panic!("expected an invalid pattern error");
+        };
+        assert!(err.to_string().starts_with("invalid pattern 'broken'"));
+
+        let err = Config::from_toml("[options.ipv4]\nkeep_prefix = 20\n");
+        assert!(matches!(err, Err(ConfigError::Options { .. })));
+        let err = Config::from_toml(
+            r#"
+            [profiles.work.options.ipv4]
+            keep_prefix = 32
+            "#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid options for 'ipv4': invalid keep_prefix 32 (expected 8, \
+             16 or 24)"
+        );
+        let config = Config::from_toml("[options.ipv4]\nkeep_prefix = 24\n");
+        assert_eq!(config.unwrap().options["ipv4"].keep_prefix, Some(24));
+
+        let err = Config::load(Path::new("/nonexistent/biip.toml"));
+        assert!(matches!(err, Err(ConfigError::Io { .. })));
+    }
+}
@@ -0,0 +1,192 @@
+//! Error types for constructing redactors and `Biip` instances.
+use std::error::Error;
+use std::{
+    fmt,
+    io,
+};
+#[cfg(feature = "config")]
+use std::path::PathBuf;
+
+/// An error raised while constructing a redactor.
+#[derive(Debug)]
+pub enum RedactorError {
+    /// A regex pattern failed to compile.
+    InvalidPattern(regex::Error),
+    /// A matcher for a set of literal strings failed to build.
+    InvalidLiterals(aho_corasick::BuildError),
+    /// An environment variable the redactor depends on is not set.
+    MissingEnv(String),
+    /// A file the redactor depends on could not be read.
+    Io(io::Error),
+    /// The redactor could not be constructed.
+    Unavailable,
+}
+
+impl fmt::Display for RedactorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RedactorError::InvalidPattern(err) => {
+                write!(f, "invalid pattern: {}", err)
+            }
+            RedactorError::InvalidLiterals(err) => {
+                write!(f, "invalid literals: {}", err)
+            }
+            RedactorError::MissingEnv(var) => {
+                write!(f, "environment variable '{}' is not set", var)
+            }
+            RedactorError::Io(err) => write!(f, "failed to read file: {}", err),
+            RedactorError::Unavailable => write!(f, "redactor is unavailable"),
+        }
+    }
+}
+
+impl Error for RedactorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RedactorError::InvalidPattern(err) => Some(err),
+            RedactorError::InvalidLiterals(err) => Some(err),
+            RedactorError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<regex::Error> for RedactorError {
+    fn from(err: regex::Error) -> Self {
+        RedactorError::InvalidPattern(err)
+    }
+}
+
+impl From<aho_corasick::BuildError> for RedactorError {
+    fn from(err: aho_corasick::BuildError) -> Self {
+        RedactorError::InvalidLiterals(err)
+    }
+}
+
+/// An error raised while building a [`Biip`](crate::Biip) instance.
+#[derive(Debug)]
+pub enum BiipError {
+    /// The named redactor failed to build.
+    Redactor {
+        name: String,
+        source: RedactorError,
+    },
+}
+
+impl fmt::Display for BiipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BiipError::Redactor { name, source } => {
+                write!(f, "failed to build redactor '{}': {}", name, source)
+            }
+        }
+    }
+}
+
+impl Error for BiipError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BiipError::Redactor { source, .. } => Some(source),
+        }
+    }
+}
+
+/// An error raised while loading a [`Config`](crate::config::Config).
+#[cfg(feature = "config")]
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The configuration file could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The configuration is not valid TOML, or has unknown or invalid
+    /// entries.
+    Parse {
+        path: Option<PathBuf>,
+        source: toml::de::Error,
+    },
+    /// The custom pattern with the given name is invalid.
+    Pattern { name: String, source: RedactorError },
+    /// The options of the redactor with the given name are invalid.
+    Options { name: String, reason: String },
+    /// There is no profile with the given name.
+    UnknownProfile(String),
+    /// The environment variable with the given name has an invalid value.
+    Env { var: String, reason: String },
+}
+
+#[cfg(feature = "config")]
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(f, "failed to read '{}': {}", path.display(), source)
+            }
+            ConfigError::Parse {
+                path: Some(path),
+                source,
+            } => {
+                write!(f, "invalid config '{}': {}", path.display(), source)
+            }
+            ConfigError::Parse { path: None, source } => {
+                write!(f, "invalid config: {}", source)
+            }
+            ConfigError::Pattern { name, source } => {
+                write!(f, "invalid pattern '{}': {}", name, source)
+            }
+            ConfigError::Options { name, reason } => {
+                write!(f, "invalid options for '{}': {}", name, reason)
+            }
+            ConfigError::UnknownProfile(name) => {
+                write!(f, "unknown profile '{}'", name)
+            }
+            ConfigError::Env { var, reason } => {
+                write!(f, "invalid {}: {}", var, reason)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            ConfigError::Parse { source, .. } => Some(source),
+            ConfigError::Pattern { source, .. } => Some(source),
+            ConfigError::Options { .. }
+            | ConfigError::UnknownProfile(_)
+            | ConfigError::Env { .. } => None,
+        }
+    }
+}
+
+/// An error raised while loading a [`Mapping`](crate::mapping::Mapping).
+#[cfg(feature = "mapping")]
+#[derive(Debug)]
+pub enum MappingError {
+    /// The mapping is not valid JSON, or not a map of strings.
+    Json(serde_json::Error),
+    /// The mapping could not be decrypted, e.g. because the key is wrong.
+    Decrypt,
+}
+
+#[cfg(feature = "mapping")]
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MappingError::Json(err) => write!(f, "invalid mapping: {}", err),
+            MappingError::Decrypt => {
+                write!(f, "failed to decrypt mapping (wrong key?)")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "mapping")]
+impl Error for MappingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MappingError::Json(err) => Some(err),
+            MappingError::Decrypt => None,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Adapters for redacting data as it flows through `std::io` readers and
+//! writers.
+//!
+//! With the `tokio` feature, async counterparts of the adapters are also
+//! available.
+use std::io::{
+    self,
+    Read,
+    Write,
+};
+
+#[cfg(feature = "tokio")]
+mod asynchronous;
+
+#[cfg(feature = "tokio")]
+pub use asynchronous::{
+    AsyncRedactingReader,
+    AsyncRedactingWriter,
+};
+
+use crate::biip::Biip;
+use crate::stream::StreamRedactor;
+
+// How much to read from a wrapped reader at a time.
+const CHUNK_SIZE: usize = 8192;
+
+/// A writer which redacts everything written to it before forwarding it to
+/// the wrapped writer.
+///
+/// Data is redacted with a [`StreamRedactor`], and so forwarded a line at a
+/// time. [`Write::flush`] forwards only complete lines, as redacting a partial
+/// line could let a value split across writes escape; the remainder is written
+/// by [`RedactingWriter::finish`], or when the writer is dropped.
+///
+/// ```
+/// use std::io::Write;
+///
+/// use biip::Biip;
+/// use biip::io::RedactingWriter;
+/// use biip::redactors::email_redactor;
+///
+/// let biip = Biip::builder()
+///     .without_defaults()
+///     .with(email_redactor())
+///     .build();
+/// let mut writer = RedactingWriter::new(Vec::new(), biip);
+/// write!(writer, "mail foo@").unwrap();
+/// writeln!(writer, "bar.com").unwrap();
+/// assert_eq!(writer.finish().unwrap(), "mail •••@•••\n".as_bytes());
+/// ```
+pub struct RedactingWriter<W: Write> {
+    // Always `Some`, until the writer is finished.
+    inner: Option<W>,
+    stream: StreamRedactor,
+    redacted: String,
+}
+
+impl<W: Write> RedactingWriter<W> {
+    /// Creates a new `RedactingWriter`, redacting with the given `Biip`.
+    pub fn new(inner: W, biip: Biip) -> Self {
+        RedactingWriter {
+            inner: Some(inner),
+            stream: StreamRedactor::with_window(biip, 0),
+            redacted: String::new(),
+        }
+    }
+
+    /// Returns a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("writer is finished")
+    }
+
+    /// Redacts and writes any buffered partial line, flushes, and returns the
+    /// wrapped writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_remaining()?;
+        let mut inner = self.inner.take().expect("writer is finished");
+        inner.flush()?;
+        Ok(inner)
+    }
+
+    fn write_remaining(&mut self) -> io::Result<()> {
+        self.redacted.clear();
+        self.stream.finish(&mut self.redacted);
+        self.write_redacted()
+    }
+
+    fn write_redacted(&mut self) -> io::Result<()> {
+        let inner = self.inner.as_mut().expect("writer is finished");
+        inner.write_all(self.redacted.as_bytes())
+    }
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.redacted.clear();
+        self.stream.push_bytes(buf, &mut self.redacted);
+        self.write_redacted()?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.as_mut().expect("writer is finished").flush()
+    }
+}
+
+impl<W: Write> Drop for RedactingWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.write_remaining();
+            let _ = self.inner.as_mut().map(Write::flush);
+        }
+    }
+}
+
+/// A reader which redacts everything read from the wrapped reader.
+///
+/// Data is redacted with a [`StreamRedactor`], and so made available a line
+/// at a time.
+///
+/// ```
+/// use std::io::Read;
+///
+/// use biip::Biip;
+/// use biip::io::RedactingReader;
+/// use biip::redactors::email_redactor;
+///
+/// let biip = Biip::builder()
+///     .without_defaults()
+///     .with(email_redactor())
+///     .build();
+/// let input = "mail foo@bar.com\n".as_bytes();
+/// let mut reader = RedactingReader::new(input, biip);
+/// let mut redacted = String::new();
+/// reader.read_to_string(&mut redacted).unwrap();
+/// assert_eq!(redacted, "mail •••@•••\n");
+/// ```
+pub struct RedactingReader<R: Read> {
+    inner: R,
+    stream: StreamRedactor,
+    chunk: Vec<u8>,
+    redacted: String,
+    // How much of `redacted` has been read.
+    pos: usize,
+    finished: bool,
+}
+
+impl<R: Read> RedactingReader<R> {
+    /// Creates a new `RedactingReader`, redacting with the given `Biip`.
+    pub fn new(inner: R, biip: Biip) -> Self {
+        RedactingReader {
+            inner,
+            stream: StreamRedactor::with_window(biip, 0),
+            chunk: vec![0; CHUNK_SIZE],
+            redacted: String::new(),
+            pos: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns the wrapped reader. Any data buffered but not yet read is
+    /// lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    // Reads and redacts more data, until some is available or the input is
+    // exhausted.
+    fn fill(&mut self) -> io::Result<()> {
+        self.redacted.clear();
+        self.pos = 0;
+        while self.redacted.is_empty() && !self.finished {
+            let n = self.inner.read(&mut self.chunk)?;
+            if n == 0 {
+                self.stream.finish(&mut self.redacted);
+                self.finished = true;
+            } else {
+                self.stream.push_bytes(&self.chunk[..n], &mut self.redacted);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for RedactingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.redacted.len() {
+            self.fill()?;
+        }
+
+        let pending = &self.redacted.as_bytes()[self.pos..];
+        let n = pending.len().min(buf.len());
+        buf[..n].copy_from_slice(&pending[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redactors;
+
+    fn email_biip() -> Biip {
+        Biip::builder()
+            .without_defaults()
+            .with(redactors::email_redactor())
+            .build()
+    }
+
+    #[test]
+    fn test_redacting_writer_buffers_until_newline() {
+        let mut writer = RedactingWriter::new(Vec::new(), email_biip());
+        writer.write_all(b"one a@b").unwrap();
+        writer.flush().unwrap();
+        assert!(writer.get_ref().is_empty());
+
+        writer.write_all(b".io\ntwo c@d.io\nthree c@").unwrap();
+        assert_eq!(writer.get_ref(), "one •••@•••\ntwo •••@•••\n".as_bytes());
+
+        let out = writer.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "one •••@•••\ntwo •••@•••\nthree c@"
+        );
+    }
+
+    #[test]
+    fn test_redacting_reader() {
+        let input = "one a@b.io\ntwo\nthree c@d.io";
+        let mut reader = RedactingReader::new(input.as_bytes(), email_biip());
+
+        // Read in small chunks, to split the multi-byte replacements.
+        let mut out = Vec::new();
+        let mut buf = [0u8; 4];
+        loop {
+            let n = reader.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "one •••@•••\ntwo\nthree •••@•••"
+        );
+    }
+
+    #[test]
+    fn test_redacting_writer_writes_partial_line_on_drop() {
+        let mut out = Vec::new();
+        {
+            let mut writer = RedactingWriter::new(&mut out, email_biip());
+            write!(writer, "to a@b.io").unwrap();
+        }
+        assert_eq!(String::from_utf8(out).unwrap(), "to •••@•••");
+    }
+}
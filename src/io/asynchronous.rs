@@ -0,0 +1,227 @@
+//! Async counterparts of the redacting readers and writers, for tokio.
+use std::io;
+use std::pin::Pin;
+use std::task::{
+    Context,
+    Poll,
+    ready,
+};
+
+use tokio::io::{
+    AsyncRead,
+    AsyncWrite,
+    ReadBuf,
+};
+
+use super::CHUNK_SIZE;
+use crate::biip::Biip;
+use crate::stream::StreamRedactor;
+
+/// An async writer which redacts everything written to it before forwarding
+/// it to the wrapped writer.
+///
+/// Like [`RedactingWriter`](super::RedactingWriter), data is forwarded a line
+/// at a time, and flushing forwards only complete lines. The remainder is
+/// written on shutdown.
+pub struct AsyncRedactingWriter<W> {
+    inner: W,
+    stream: StreamRedactor,
+    redacted: String,
+    // Redacted data which is yet to be written to `inner`.
+    pending: Vec<u8>,
+    finished: bool,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncRedactingWriter<W> {
+    /// Creates a new `AsyncRedactingWriter`, redacting with the given `Biip`.
+    pub fn new(inner: W, biip: Biip) -> Self {
+        AsyncRedactingWriter {
+            inner,
+            stream: StreamRedactor::with_window(biip, 0),
+            redacted: String::new(),
+            pending: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// Returns a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the wrapped writer. Any buffered data is lost, so the writer
+    /// should be shut down first.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    // Writes out as much of the pending data as possible.
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.pending.is_empty() {
+            let inner = Pin::new(&mut self.inner);
+            let n = ready!(inner.poll_write(cx, &self.pending))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.pending.drain(..n);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncRedactingWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        // Apply backpressure: only accept more data once the previous data
+        // has been written out.
+        ready!(this.poll_pending(cx))?;
+
+        this.redacted.clear();
+        this.stream.push_bytes(buf, &mut this.redacted);
+        this.pending.extend_from_slice(this.redacted.as_bytes());
+        if let Poll::Ready(Err(err)) = this.poll_pending(cx) {
+            return Poll::Ready(Err(err));
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if !this.finished {
+            this.redacted.clear();
+            this.stream.finish(&mut this.redacted);
+            this.pending.extend_from_slice(this.redacted.as_bytes());
+            this.finished = true;
+        }
+        ready!(this.poll_pending(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+/// An async reader which redacts everything read from the wrapped reader.
+///
+/// Like [`RedactingReader`](super::RedactingReader), redacted data is made
+/// available a line at a time.
+pub struct AsyncRedactingReader<R> {
+    inner: R,
+    stream: StreamRedactor,
+    chunk: Vec<u8>,
+    redacted: String,
+    // How much of `redacted` has been read.
+    pos: usize,
+    finished: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRedactingReader<R> {
+    /// Creates a new `AsyncRedactingReader`, redacting with the given `Biip`.
+    pub fn new(inner: R, biip: Biip) -> Self {
+        AsyncRedactingReader {
+            inner,
+            stream: StreamRedactor::with_window(biip, 0),
+            chunk: vec![0; CHUNK_SIZE],
+            redacted: String::new(),
+            pos: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns the wrapped reader. Any data buffered but not yet read is
+    /// lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    // Reads and redacts more data, until some is available or the input is
+    // exhausted.
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.pos >= self.redacted.len() && !self.finished {
+            let mut chunk = ReadBuf::new(&mut self.chunk);
+            ready!(Pin::new(&mut self.inner).poll_read(cx, &mut chunk))?;
+
+            self.redacted.clear();
+            self.pos = 0;
+            if chunk.filled().is_empty() {
+                self.stream.finish(&mut self.redacted);
+                self.finished = true;
+            } else {
+                self.stream.push_bytes(chunk.filled(), &mut self.redacted);
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncRedactingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_fill(cx))?;
+
+        let pending = &this.redacted.as_bytes()[this.pos..];
+        let n = pending.len().min(buf.remaining());
+        buf.put_slice(&pending[..n]);
+        this.pos += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{
+        AsyncReadExt,
+        AsyncWriteExt,
+    };
+
+    use super::*;
+    use crate::redactors;
+
+    fn email_biip() -> Biip {
+        Biip::builder()
+            .without_defaults()
+            .with(redactors::email_redactor())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_async_redacting_writer() {
+        let mut writer = AsyncRedactingWriter::new(Vec::new(), email_biip());
+        writer.write_all(b"one a@b").await.unwrap();
+        writer.flush().await.unwrap();
+        assert!(writer.get_ref().is_empty());
+
+        writer.write_all(b".io\ntwo c@d.io").await.unwrap();
+        writer.shutdown().await.unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "one •••@•••\ntwo •••@•••"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_redacting_reader() {
+        let input = "one a@b.io\ntwo\nthree c@d.io".as_bytes();
+        let mut reader = AsyncRedactingReader::new(input, email_biip());
+        let mut out = String::new();
+        reader.read_to_string(&mut out).await.unwrap();
+        assert_eq!(out, "one •••@•••\ntwo\nthree •••@•••");
+    }
+}
@@ -38,8 +38,61 @@
 //! assert!(redacted.contains("My secret is ••••⚿•."));
 //! ```
 pub mod biip;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod error;
+pub mod io;
+pub mod mapping;
+mod normalize;
+mod prefilter;
 pub mod redactor;
 pub mod redactors;
+pub mod report;
+pub mod spec;
+pub mod stream;
+pub mod style;
 
-pub use biip::Biip;
-pub use redactor::Redactor;
+pub use biip::{
+    Biip,
+    BiipBuilder,
+};
+#[cfg(feature = "config")]
+pub use config::Config;
+#[cfg(feature = "config")]
+pub use error::ConfigError;
+#[cfg(feature = "mapping")]
+pub use error::MappingError;
+pub use error::{
+    BiipError,
+    RedactorError,
+};
+pub use mapping::Mapping;
+pub use redactor::{
+    Match,
+    Named,
+    PatternOptions,
+    Redact,
+    Redactor,
+    ReplaceFn,
+    Severity,
+    Validator,
+};
+pub use report::{
+    Detection,
+    Redaction,
+    Report,
+};
+pub use spec::{
+    RedactorSpec,
+    ValidatorKind,
+};
+
+/// Processes a string with the shared default [`Biip`] instance (see
+/// [`Biip::global`]).
+///
+/// ```
+/// assert_eq!(biip::process("Mail foo@bar.com"), "Mail •••@•••");
+/// ```
+pub fn process(text: &str) -> String {
+    Biip::global().process(text)
+}
@@ -0,0 +1,205 @@
+//! Mappings of placeholders back to the values they replaced, so that a
+//! redaction can be reversed.
+//!
+//! A mapping is only useful with a style whose placeholders identify values,
+//! i.e. [`Style::Numbered`](crate::style::Style::Numbered) or
+//! [`Style::Pseudonyms`](crate::style::Style::Pseudonyms).
+//!
+//! ```
+//! use biip::Biip;
+//! use biip::redactors::email_redactor;
+//! use biip::style::Style;
+//!
+//! let biip = Biip::builder()
+//!     .without_defaults()
+//!     .with_named("email", email_redactor())
+//!     .with_style(Style::Numbered)
+//!     .with_mapping()
+//!     .build();
+//! let redacted = biip.process("Mail foo@bar.com");
+//! assert_eq!(redacted, "Mail [EMAIL-1]");
+//!
+//! let mapping = biip.mapping().unwrap();
+//! assert_eq!(mapping.unredact(&redacted), "Mail foo@bar.com");
+//! ```
+use std::collections::BTreeMap;
+
+use aho_corasick::{
+    AhoCorasick,
+    MatchKind,
+};
+#[cfg(feature = "mapping")]
+use chacha20poly1305::aead::{
+    Aead,
+    AeadCore,
+    KeyInit,
+    OsRng,
+};
+#[cfg(feature = "mapping")]
+use chacha20poly1305::{
+    ChaCha20Poly1305,
+    Key,
+    Nonce,
+};
+#[cfg(feature = "mapping")]
+use hmac::{
+    Hmac,
+    Mac,
+};
+#[cfg(feature = "serde")]
+use serde::{
+    Deserialize,
+    Serialize,
+};
+#[cfg(feature = "mapping")]
+use sha2::Sha256;
+
+#[cfg(feature = "mapping")]
+use crate::error::MappingError;
+
+// The length of the nonce prefixed to encrypted mappings.
+#[cfg(feature = "mapping")]
+const NONCE_LEN: usize = 12;
+
+/// The values replaced by each placeholder.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Mapping {
+    values: BTreeMap<String, String>,
+}
+
+impl Mapping {
+    /// Creates an empty mapping.
+    pub fn new() -> Mapping {
+        Mapping::default()
+    }
+
+    /// Records that `placeholder` replaced `value`. If the placeholder was
+    /// already recorded, the first value is kept.
+    pub fn insert(&mut self, placeholder: &str, value: &str) {
+        if !self.values.contains_key(placeholder) {
+            self.values
+                .insert(placeholder.to_string(), value.to_string());
+        }
+    }
+
+    /// Returns the value replaced by `placeholder`, if any.
+    pub fn get(&self, placeholder: &str) -> Option<&str> {
+        self.values.get(placeholder).map(String::as_str)
+    }
+
+    /// Returns the number of placeholders.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if there are no placeholders.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Restores the original values of the placeholders in `text`.
+    pub fn unredact(&self, text: &str) -> String {
+        if self.values.is_empty() {
+            return text.to_string();
+        }
+        // Prefer the longest placeholder, e.g. `[EMAIL-12]` over `[EMAIL-1]`.
+        let placeholders = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(self.values.keys())
+            .expect("placeholders are valid patterns");
+        let values: Vec<&String> = self.values.values().collect();
+        placeholders.replace_all(text, &values)
+    }
+
+    /// Serializes the mapping as JSON.
+    #[cfg(feature = "mapping")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("a map of strings serializes")
+    }
+
+    /// Parses a mapping from JSON.
+    #[cfg(feature = "mapping")]
+    pub fn from_json(json: &str) -> Result<Mapping, MappingError> {
+        serde_json::from_str(json).map_err(MappingError::Json)
+    }
+
+    /// Encrypts the mapping with `key` (of any length), e.g. for saving it
+    /// next to the redacted text.
+    #[cfg(feature = "mapping")]
+    pub fn encrypt(&self, key: &[u8]) -> Vec<u8> {
+        let cipher = cipher(key);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let encrypted = cipher
+            .encrypt(&nonce, self.to_json().as_bytes())
+            .expect("encryption does not fail");
+        let mut data = nonce.to_vec();
+        data.extend(encrypted);
+        data
+    }
+
+    /// Decrypts a mapping encrypted with [`Mapping::encrypt`], failing if the
+    /// key is wrong or the data has been tampered with.
+    #[cfg(feature = "mapping")]
+    pub fn decrypt(data: &[u8], key: &[u8]) -> Result<Mapping, MappingError> {
+        if data.len() < NONCE_LEN {
+            return Err(MappingError::Decrypt);
+        }
+        let (nonce, encrypted) = data.split_at(NONCE_LEN);
+        let json = cipher(key)
+            .decrypt(Nonce::from_slice(nonce), encrypted)
+            .map_err(|_| MappingError::Decrypt)?;
+        let json =
+            String::from_utf8(json).map_err(|_| MappingError::Decrypt)?;
+        Mapping::from_json(&json)
+    }
+}
+
+// Derives the cipher for a key of any length.
+#[cfg(feature = "mapping")]
+fn cipher(key: &[u8]) -> ChaCha20Poly1305 {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key)
+        .expect("HMAC accepts keys of any length");
+    mac.update(b"biip mapping");
+    let key = mac.finalize().into_bytes();
+    ChaCha20Poly1305::new(Key::from_slice(&key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unredact() {
+        let mut mapping = Mapping::new();
+        mapping.insert("[EMAIL-1]", "a@b.io");
+        mapping.insert("[EMAIL-12]", "c@d.io");
+        mapping.insert("[EMAIL-1]", "e@f.io");
+        assert_eq!(mapping.len(), 2);
+        assert_eq!(mapping.get("[EMAIL-1]"), Some("a@b.io"));
+        assert_eq!(
+            mapping.unredact("[EMAIL-12], [EMAIL-1] and [EMAIL-2]"),
+            "c@d.io, a@b.io and [EMAIL-2]"
+        );
+        assert_eq!(Mapping::new().unredact("[EMAIL-1]"), "[EMAIL-1]");
+    }
+
+    #[cfg(feature = "mapping")]
+    #[test]
+    fn test_json_and_encryption() {
+        let mut mapping = Mapping::new();
+        mapping.insert("email:3fa2c1", "a@b.io");
+        assert_eq!(Mapping::from_json(&mapping.to_json()).unwrap(), mapping);
+
+        let data = mapping.encrypt(b"key");
+        assert!(!data.windows(6).any(|w| w == b"a@b.io"));
+        assert_eq!(Mapping::decrypt(&data, b"key").unwrap(), mapping);
+        assert!(matches!(
+            Mapping::decrypt(&data, b"other"),
+            Err(MappingError::Decrypt)
+        ));
+        assert!(Mapping::from_json("[]").is_err());
+    }
+}
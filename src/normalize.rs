@@ -0,0 +1,140 @@
+//! A normalized shadow of a text, to find values obfuscated with lookalike
+//! characters, e.g. a fullwidth `＠` in an email or non-breaking spaces in a
+//! phone number, or percent-encoded in URLs, e.g. `user%40example.com`.
+//!
+//! Like Unicode's NFKC normalization, but only for characters which have an
+//! ASCII lookalike, one at a time, so that the offsets of matches in the
+//! shadow can be mapped back to the original text (whose other characters are
+//! left untouched). Likewise, only percent-encoded ASCII characters are
+//! decoded.
+use crate::redactor::Match;
+
+/// A normalized copy of a text, and the offsets of its characters in the
+/// original text.
+pub(crate) struct Shadow {
+    text: String,
+    // The start and end offsets in the original text of the character at each
+    // byte of the shadow.
+    starts: Vec<usize>,
+    ends: Vec<usize>,
+}
+
+impl Shadow {
+    /// Returns the shadow of `text`, or `None` if it has nothing to normalize.
+    pub(crate) fn new(text: &str) -> Option<Shadow> {
+        let encoded = text.contains('%');
+        if !encoded && !text.chars().any(|c| fold(c) != Some(c)) {
+            return None;
+        }
+        let mut shadow = Shadow {
+            text: String::with_capacity(text.len()),
+            starts: Vec::with_capacity(text.len()),
+            ends: Vec::with_capacity(text.len()),
+        };
+        let mut chars = text.char_indices();
+        while let Some((start, c)) = chars.next() {
+            let (folded, end) = match percent_decode(&text[start..]) {
+                Some(decoded) => {
+                    chars.nth(1);
+                    (decoded, start + 3)
+                }
+                None => match fold(c) {
+                    Some(folded) => (folded, start + c.len_utf8()),
+                    None => continue,
+                },
+            };
+            shadow.text.push(folded);
+            for _ in 0..folded.len_utf8() {
+                shadow.starts.push(start);
+                shadow.ends.push(end);
+            }
+        }
+        (shadow.text != text).then_some(shadow)
+    }
+
+    /// The normalized text.
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Maps a match in the shadow to the same characters in the original
+    /// text.
+    pub(crate) fn original(&self, m: Match) -> Match {
+        if m.start >= m.end {
+            return m;
+        }
+        Match {
+            start: self.starts[m.start],
+            end: self.ends[m.end - 1],
+            replacement: m.replacement,
+        }
+    }
+}
+
+// The (printable ASCII) character percent-encoded at the start of `text`, if
+// any, e.g. `@` for `%40`.
+fn percent_decode(text: &str) -> Option<char> {
+    let hex = text.strip_prefix('%')?.get(..2)?;
+    let byte = u8::from_str_radix(hex, 16).ok()?;
+    (byte == b' ' || byte.is_ascii_graphic()).then_some(byte as char)
+}
+
+// The ASCII lookalike of a character, if any, or `None` for invisible ones
+// (e.g. zero width spaces) which are dropped.
+fn fold(c: char) -> Option<char> {
+    let code = c as u32;
+    let folded = match code {
+        // Fullwidth forms, e.g. `＠` and `１`.
+        0xFF01..=0xFF5E => code - 0xFEE0,
+        // Spaces, e.g. non-breaking and ideographic ones.
+        0x00A0 | 0x1680 | 0x2000..=0x200A | 0x202F | 0x205F | 0x3000 => 0x20,
+        // Small and other lookalikes of `@`, `.` and `-`.
+        0xFE6B => 0x40,
+        0xFE52 | 0x2024 => 0x2E,
+        0x2010..=0x2015 | 0x2212 | 0xFE63 => 0x2D,
+        // Digits of other scripts, e.g. Arabic-Indic and Devanagari.
+        0x0660..=0x0669 => 0x30 + (code - 0x0660),
+        0x06F0..=0x06F9 => 0x30 + (code - 0x06F0),
+        0x0966..=0x096F => 0x30 + (code - 0x0966),
+        0x09E6..=0x09EF => 0x30 + (code - 0x09E6),
+        // Mathematical digits, e.g. `𝟏`, in five styles.
+        0x1D7CE..=0x1D7FF => 0x30 + (code - 0x1D7CE) % 10,
+        // Superscript and subscript digits.
+        0x00B9 => 0x31,
+        0x00B2 | 0x00B3 => 0x30 + (code - 0x00B0),
+        0x2070 | 0x2074..=0x2079 => 0x30 + (code - 0x2070),
+        0x2080..=0x2089 => 0x30 + (code - 0x2080),
+        // Invisible characters, e.g. zero width spaces and soft hyphens.
+        0x00AD | 0x200B..=0x200D | 0x2060 | 0xFEFF => return None,
+        _ => return Some(c),
+    };
+    char::from_u32(folded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shadow() {
+        assert!(Shadow::new("plain text").is_none());
+        assert!(Shadow::new("café ☕").is_none());
+
+        let text = "é a\u{200B}＠b\u{00A0}١٢３";
+        let shadow = Shadow::new(text).unwrap();
+        assert_eq!(shadow.text(), "é a@b 123");
+
+        let at = shadow.text().find('@').unwrap();
+        let m = shadow.original(Match::new(at - 1, at + 2, "•"));
+        assert_eq!(&text[m.start..m.end], "a\u{200B}＠b");
+        let m = shadow.original(Match::new(at + 3, shadow.text().len(), "•"));
+        assert_eq!(&text[m.start..m.end], "١٢３");
+
+        assert!(Shadow::new("100% sure, %zz %C3%A9").is_none());
+        let text = "?to=%22a%40b.io%22&n=%2Btoken";
+        let shadow = Shadow::new(text).unwrap();
+        assert_eq!(shadow.text(), "?to=\"a@b.io\"&n=+token");
+        let m = shadow.original(Match::new(5, 11, "•"));
+        assert_eq!(&text[m.start..m.end], "a%40b.io");
+    }
+}
@@ -0,0 +1,59 @@
+//! A prefilter to skip redactors which cannot match a text.
+use regex::{
+    RegexSet,
+    RegexSetBuilder,
+};
+
+use crate::redactor::Redact;
+
+/// A `RegexSet` over the patterns of all redactors which expose one.
+///
+/// Running a single set over a text is much cheaper than running every
+/// redactor, and most lines of a log have nothing to redact. The set is built
+/// case-insensitive, as a redactor's pattern string doesn't carry the options
+/// its regex was built with; this can only let extra redactors through.
+pub(crate) struct Prefilter {
+    set: RegexSet,
+    // The redactor index for each pattern in the set.
+    indices: Vec<usize>,
+    // The number of redactors, including the ones without a pattern.
+    len: usize,
+}
+
+impl Prefilter {
+    /// Builds a prefilter for the redactors. Returns `None` if none of them
+    /// has a pattern, or if the set fails to compile (e.g. it is too large).
+    pub(crate) fn new(redactors: &[Box<dyn Redact>]) -> Option<Prefilter> {
+        let (indices, patterns): (Vec<usize>, Vec<String>) = redactors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, r)| r.pattern().map(|p| (index, p)))
+            .unzip();
+        if patterns.is_empty() {
+            return None;
+        }
+
+        let set = RegexSetBuilder::new(&patterns)
+            .case_insensitive(true)
+            .build()
+            .ok()?;
+        Some(Prefilter {
+            set,
+            indices,
+            len: redactors.len(),
+        })
+    }
+
+    /// Returns, for each redactor, whether it may have a match in the text.
+    /// Redactors without a pattern may always match.
+    pub(crate) fn candidates(&self, text: &str) -> Vec<bool> {
+        let mut candidates = vec![true; self.len];
+        for &index in &self.indices {
+            candidates[index] = false;
+        }
+        for matched in self.set.matches(text).iter() {
+            candidates[self.indices[matched]] = true;
+        }
+        candidates
+    }
+}
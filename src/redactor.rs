@@ -1,6 +1,224 @@
 use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
 
-use regex::Regex;
+use aho_corasick::{
+    AhoCorasick,
+    MatchKind,
+};
+use regex::{
+    Regex,
+    RegexBuilder,
+};
+#[cfg(feature = "serde")]
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::error::RedactorError;
+
+/// How sensitive the values a redactor finds are, from least to most.
+///
+/// A minimum severity (see
+/// [`BiipBuilder::with_min_severity`](crate::BiipBuilder::with_min_severity))
+/// skips the redactors below it, e.g. to only redact secrets in output for
+/// developers, but everything in exported reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Severity {
+    /// Values which only identify someone in combination with others, e.g.
+    /// IP addresses or UUIDs.
+    QuasiIdentifier,
+    /// Values which identify someone or something, e.g. emails or usernames.
+    Identifier,
+    /// Values which grant access, e.g. passwords, tokens and API keys.
+    Secret,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::QuasiIdentifier => write!(f, "quasi_identifier"),
+            Severity::Identifier => write!(f, "identifier"),
+            Severity::Secret => write!(f, "secret"),
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Severity, String> {
+        match s {
+            "quasi_identifier" => Ok(Severity::QuasiIdentifier),
+            "identifier" => Ok(Severity::Identifier),
+            "secret" => Ok(Severity::Secret),
+            _ => Err(format!("unknown severity '{}'", s)),
+        }
+    }
+}
+
+/// A rule for redacting sensitive information from text.
+///
+/// The built-in [`Redactor`] variants implement this trait, and downstream
+/// crates can implement it to plug their own logic (e.g. a dictionary lookup)
+/// into [`Biip`](crate::Biip).
+///
+/// # Example
+///
+/// ```
+/// use biip::{
+///     Biip,
+///     Match,
+///     Redact,
+/// };
+///
+/// struct Shouty;
+///
+/// impl Redact for Shouty {
+///     fn name(&self) -> &str {
+///         "shouty"
+///     }
+///
+///     fn find(&self, text: &str) -> Vec<Match> {
+///         text.match_indices("HELLO")
+///             .map(|(start, _)| Match::new(start, start + 5, "hello"))
+///             .collect()
+///     }
+/// }
+///
+/// let biip = Biip::builder().without_defaults().with_custom(Shouty).build();
+/// assert_eq!(biip.process("HELLO world"), "hello world");
+/// ```
+pub trait Redact: Send + Sync {
+    /// A short name identifying the redactor, e.g. `email`.
+    fn name(&self) -> &str {
+        "custom"
+    }
+
+    /// A regex pattern matching (at least) everything this redactor would
+    /// redact, if it has one.
+    ///
+    /// When available, it is used to cheaply skip the redactor for text that
+    /// can't have any matches. Defaults to `None`, which means the redactor
+    /// always runs.
+    fn pattern(&self) -> Option<String> {
+        None
+    }
+
+    /// How sensitive the values this redactor finds are. Defaults to
+    /// [`Severity::Secret`], so that custom redactors are never skipped by a
+    /// minimum severity unless they say so.
+    fn severity(&self) -> Severity {
+        Severity::Secret
+    }
+
+    /// Finds the redactions to be made in the given text.
+    ///
+    /// The returned matches must be in ascending order and must not overlap.
+    fn find(&self, text: &str) -> Vec<Match>;
+
+    /// Applies the redactor to a given text.
+    ///
+    /// Returns `Cow::Borrowed` when nothing was redacted, so that unchanged
+    /// text is never copied.
+    fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        apply(text, &self.find(text))
+    }
+}
+
+/// A single redaction: the byte range of the sensitive text, and what it
+/// should be replaced with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// The byte offset where the match starts.
+    pub start: usize,
+    /// The byte offset where the match ends (exclusive).
+    pub end: usize,
+    /// The replacement for the matched text.
+    pub replacement: String,
+}
+
+impl Match {
+    /// Creates a new `Match`.
+    pub fn new(start: usize, end: usize, replacement: &str) -> Self {
+        Match {
+            start,
+            end,
+            replacement: replacement.to_string(),
+        }
+    }
+}
+
+/// Replaces each of the (ordered, non-overlapping) matches in `text`.
+///
+/// Returns `Cow::Borrowed` if there are no matches.
+pub fn apply<'a>(text: &'a str, matches: &[Match]) -> Cow<'a, str> {
+    if matches.is_empty() {
+        return Cow::Borrowed(text);
+    }
+
+    let mut owned = String::with_capacity(text.len());
+    apply_into(text, matches, &mut owned);
+    Cow::Owned(owned)
+}
+
+/// Replaces each of the (ordered, non-overlapping) matches in `text`,
+/// appending the result to `out`.
+pub fn apply_into(text: &str, matches: &[Match], out: &mut String) {
+    let mut last_end = 0;
+    for m in matches {
+        out.push_str(&text[last_end..m.start]);
+        out.push_str(&m.replacement);
+        last_end = m.end;
+    }
+    out.push_str(&text[last_end..]);
+}
+
+/// A redactor with an explicit name, wrapping any other [`Redact`]
+/// implementation.
+pub struct Named<R> {
+    name: String,
+    severity: Severity,
+    redactor: R,
+}
+
+impl<R: Redact> Named<R> {
+    /// Creates a new `Named` redactor, with the severity of the wrapped one.
+    pub fn new(name: &str, redactor: R) -> Self {
+        Named {
+            name: name.to_string(),
+            severity: redactor.severity(),
+            redactor,
+        }
+    }
+
+    /// Sets the severity of the redactor.
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl<R: Redact> Redact for Named<R> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn pattern(&self) -> Option<String> {
+        self.redactor.pattern()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn find(&self, text: &str) -> Vec<Match> {
+        self.redactor.find(text)
+    }
+}
 
 /// An enum representing a redaction rule.
 ///
@@ -11,6 +229,11 @@ pub enum Redactor {
     /// The first `String` is the pattern to find, and the second is the
     /// replacement.
     Simple(String, String),
+    /// A replacement of any of a set of literal strings, matched with
+    /// Aho-Corasick. This scales to many literals (e.g. secrets from the
+    /// environment) much better than a regex alternation.
+    /// The `String` is the replacement.
+    Literals(AhoCorasick, String),
     /// A regex-based replacement.
     /// The `Regex` is the pattern to find, and the `String` is the
     /// replacement.
@@ -21,9 +244,40 @@ pub enum Redactor {
     ReWithCapture(Regex, String),
     /// A regex that finds candidates, which are then passed to a validator
     /// function. Only if the validator returns true is the match redacted.
-    Validated(Regex, fn(&str) -> bool, String),
+    Validated(Regex, Validator, String),
+    /// A regex-based replacement where the replacement is computed from the
+    /// matched text, e.g. to keep the last few digits or hash the value.
+    ReWithReplacer(Regex, ReplaceFn),
+    /// Like `Validated`, but with the replacement computed from the matched
+    /// text, as in `ReWithReplacer`.
+    ValidatedWithReplacer(Regex, Validator, ReplaceFn),
+    /// A regex that finds regions of text (e.g. documents), which are then
+    /// passed to a validator function. Only within the regions it accepts,
+    /// the inner `Redactor` is applied, e.g. to redact the values of one kind
+    /// of document only.
+    Within(Regex, Validator, Box<Redactor>),
 }
 
+/// Options for building a [`Redactor`] from a pattern string, with
+/// [`Redactor::from_pattern`].
+#[derive(Debug, Clone, Default)]
+pub struct PatternOptions {
+    /// The replacement string. If `None`, a default replacer will be used.
+    pub beep: Option<String>,
+    /// Whether the pattern matches case-insensitively.
+    pub case_insensitive: bool,
+    /// Whether capture group references like `$1` in `beep` are expanded.
+    pub expand_captures: bool,
+}
+
+/// A function deciding whether a candidate match should be redacted.
+///
+/// Validators can capture state, e.g. an allowlist loaded from configuration.
+pub type Validator = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// A function computing the replacement for a matched text.
+pub type ReplaceFn = Box<dyn Fn(&str) -> String + Send + Sync>;
+
 impl Redactor {
     /// Creates a new `Redactor::Simple` variant.
     ///
@@ -37,6 +291,30 @@ impl Redactor {
         Redactor::Simple(pattern, replacer)
     }
 
+    /// Creates a new `Redactor::Literals` variant.
+    ///
+    /// Where literals overlap, the longest one is redacted.
+    ///
+    /// # Arguments
+    ///
+    /// * `literals` - The literal strings to search for.
+    /// * `beep` - An optional replacement string. If `None`, a default replacer
+    ///   will be used.
+    pub fn literals<I, P>(
+        literals: I,
+        beep: Option<String>,
+    ) -> Result<Self, RedactorError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        let matcher = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(literals)?;
+        let replacer = beep.unwrap_or(String::from("•••"));
+        Ok(Redactor::Literals(matcher, replacer))
+    }
+
     /// Creates a new `Redactor::Re` variant.
     ///
     /// # Arguments
@@ -49,6 +327,85 @@ impl Redactor {
         Redactor::Re(pattern, replacer)
     }
 
+    /// Creates a regex-based `Redactor` from a pattern string, e.g. one
+    /// supplied by a user, failing if the pattern is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The regex pattern to search for.
+    /// * `options` - How to compile the pattern, and what to replace it with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use biip::{
+    ///     PatternOptions,
+    ///     Redactor,
+    /// };
+    ///
+    /// let options = PatternOptions {
+    ///     case_insensitive: true,
+    ///     ..Default::default()
+    /// };
+    /// let redactor = Redactor::from_pattern(r"acme-\d+", options).unwrap();
+    /// assert_eq!(redactor.redact("ticket ACME-42"), "ticket •••");
+    ///
+    /// let invalid = Redactor::from_pattern("(", PatternOptions::default());
+    /// assert!(invalid.is_err());
+    /// ```
+    pub fn from_pattern(
+        pattern: &str,
+        options: PatternOptions,
+    ) -> Result<Self, RedactorError> {
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(options.case_insensitive)
+            .build()?;
+        if options.expand_captures {
+            let replacer = options.beep.unwrap_or(String::from("•••"));
+            Ok(Redactor::regex_with_capture(regex, replacer))
+        } else {
+            Ok(Redactor::regex(regex, options.beep))
+        }
+    }
+
+    /// Creates a `Redactor::Re` which redacts blocks of text, possibly
+    /// spanning multiple lines, from a line matching `begin` through the next
+    /// one matching `end` (e.g. PEM blocks).
+    ///
+    /// A block which is never ended is left alone, as the text following a
+    /// lone `begin` (e.g. in source code or documentation) is unrelated.
+    ///
+    /// # Arguments
+    ///
+    /// * `begin` - The regex pattern marking the beginning of the block.
+    /// * `end` - The regex pattern marking the end of the block.
+    /// * `beep` - An optional replacement string. If `None`, a default replacer
+    ///   will be used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use biip::Redactor;
+    ///
+    /// let redactor = Redactor::block(
+    ///     "-----BEGIN SECRET-----",
+    ///     "-----END SECRET-----",
+    ///     None,
+    /// )
+    /// .unwrap();
+    /// let text = "a\n-----BEGIN SECRET-----\nc2VjcmV0\n\
+    ///             -----END SECRET-----\nb";
+    /// assert_eq!(redactor.redact(text), "a\n•••\nb");
+    /// ```
+    pub fn block(
+        begin: &str,
+        end: &str,
+        beep: Option<String>,
+    ) -> Result<Self, RedactorError> {
+        let pattern = format!(r"(?s)(?:{}).*?(?:{})", begin, end);
+        Ok(Redactor::regex(Regex::new(&pattern)?, beep))
+    }
+
     /// Creates a new `Redactor::ReWithCapture` variant.
     ///
     /// # Arguments
@@ -64,16 +421,104 @@ impl Redactor {
     /// # Arguments
     ///
     /// * `pattern` - The regex pattern to search for.
-    /// * `validator` - A function to validate the redacted text.
+    /// * `validator` - A function (or closure) to validate the redacted text.
     /// * `beep` - An optional replacement string. If `None`, a default replacer
     ///   will be used.
-    pub fn validated(
+    pub fn validated<F>(
         pattern: Regex,
-        validator: fn(&str) -> bool,
+        validator: F,
         beep: Option<String>,
-    ) -> Self {
+    ) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
         let replacer = beep.clone().unwrap_or(String::from("•••"));
-        Redactor::Validated(pattern, validator, replacer)
+        Redactor::Validated(pattern, Box::new(validator), replacer)
+    }
+
+    /// Creates a new `Redactor::ReWithReplacer` variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The regex pattern to search for.
+    /// * `replacer` - A function (or closure) which receives the matched text
+    ///   and returns its replacement.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use biip::Redactor;
+    /// use regex::Regex;
+    ///
+    /// // Keep the last 4 digits of a card number.
+    /// let redactor = Redactor::with_replacer(
+    ///     Regex::new(r"\b\d{12}(\d{4})\b").unwrap(),
+    ///     |m| format!("••••{}", &m[m.len() - 4..]),
+    /// );
+    /// assert_eq!(redactor.redact("4111111111111111"), "••••1111");
+    /// ```
+    pub fn with_replacer<F>(pattern: Regex, replacer: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        Redactor::ReWithReplacer(pattern, Box::new(replacer))
+    }
+
+    /// Creates a new `Redactor::ValidatedWithReplacer` variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The regex pattern to search for.
+    /// * `validator` - A function (or closure) to validate the redacted text.
+    /// * `replacer` - A function (or closure) computing the replacement from
+    ///   the matched text.
+    pub fn validated_with_replacer<V, F>(
+        pattern: Regex,
+        validator: V,
+        replacer: F,
+    ) -> Self
+    where
+        V: Fn(&str) -> bool + Send + Sync + 'static,
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        Redactor::ValidatedWithReplacer(
+            pattern,
+            Box::new(validator),
+            Box::new(replacer),
+        )
+    }
+
+    /// Creates a new `Redactor::Within` variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The regex pattern finding the regions to search in.
+    /// * `validator` - A function (or closure) to validate each region.
+    /// * `inner` - The redactor to apply within the regions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use biip::Redactor;
+    /// use regex::Regex;
+    ///
+    /// // Redact the values of `pass` entries in `[prod]` sections only.
+    /// let redactor = Redactor::within(
+    ///     Regex::new(r"(?m)^\[\w+\](?:\n[^\[\n].*)*").unwrap(),
+    ///     |section| section.starts_with("[prod]"),
+    ///     Redactor::regex(Regex::new(r"(?m)^pass=.*").unwrap(), None),
+    /// );
+    /// let text = "[dev]\npass=dev\n[prod]\nuser=root\npass=hunter2";
+    /// assert_eq!(
+    ///     redactor.redact(text),
+    ///     "[dev]\npass=dev\n[prod]\nuser=root\n•••"
+    /// );
+    /// ```
+    pub fn within<F>(pattern: Regex, validator: F, inner: Redactor) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        Redactor::Within(pattern, Box::new(validator), Box::new(inner))
     }
 
     /// Applies the redactor to a given text.
@@ -86,54 +531,170 @@ impl Redactor {
     ///
     /// A new `String` with the redactions applied.
     pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        apply(text, &Redact::find(self, text))
+    }
+}
+
+impl Redact for Redactor {
+    fn pattern(&self) -> Option<String> {
         match self {
-            Redactor::Simple(pattern, replacer) => {
-                if text.contains(pattern) {
-                    Cow::Owned(text.replace(pattern, replacer))
-                } else {
-                    Cow::Borrowed(text)
-                }
+            Redactor::Simple(pattern, _) => Some(regex::escape(pattern)),
+            // An alternation of many literals is what this variant avoids.
+            Redactor::Literals(..) => None,
+            Redactor::Re(pattern, _)
+            | Redactor::ReWithCapture(pattern, _)
+            | Redactor::Validated(pattern, _, _)
+            | Redactor::ReWithReplacer(pattern, _)
+            | Redactor::ValidatedWithReplacer(pattern, _, _)
+            | Redactor::Within(pattern, _, _) => {
+                Some(pattern.as_str().to_string())
             }
-            Redactor::Re(pattern, replacer)
-            | Redactor::ReWithCapture(pattern, replacer) => {
-                pattern.replace_all(text, replacer.as_str())
-            }
-            Redactor::Validated(pattern, validator, replacer) => {
-                let mut owned: Option<String> = None;
-                let mut last_end = 0;
-
-                for m in pattern.find_iter(text) {
-                    if validator(m.as_str()) {
-                        // First time we find a valid match, we must allocate.
-                        if owned.is_none() {
-                            owned = Some(String::with_capacity(text.len()));
-                        }
-                        let owned_str = owned.as_mut().unwrap();
-
-                        // Append the text from the end of the last match to the
-                        // start of this one.
-                        owned_str.push_str(&text[last_end..m.start()]);
-                        // Append the replacement string.
-                        owned_str.push_str(replacer);
-                        // Update our position.
-                        last_end = m.end();
-                    }
-                }
-
-                // If `owned` is Some, it means we performed at least one
-                // redaction. We finish by appending the
-                // remainder of the original string.
-                match owned {
-                    Some(mut s) => {
-                        s.push_str(&text[last_end..]);
-                        Cow::Owned(s)
+        }
+    }
+
+    fn find(&self, text: &str) -> Vec<Match> {
+        match self {
+            Redactor::Simple(pattern, replacer) => text
+                .match_indices(pattern.as_str())
+                .map(|(start, m)| Match::new(start, start + m.len(), replacer))
+                .collect(),
+            Redactor::Literals(matcher, replacer) => matcher
+                .find_iter(text)
+                .map(|m| Match::new(m.start(), m.end(), replacer))
+                .collect(),
+            Redactor::Re(pattern, replacer) => pattern
+                .find_iter(text)
+                .map(|m| Match::new(m.start(), m.end(), replacer))
+                .collect(),
+            Redactor::ReWithCapture(pattern, replacer) => pattern
+                .captures_iter(text)
+                .map(|caps| {
+                    // Group 0 always participates in a match.
+                    let m = caps.get(0).unwrap();
+                    let mut replacement = String::new();
+                    caps.expand(replacer, &mut replacement);
+                    Match {
+                        start: m.start(),
+                        end: m.end(),
+                        replacement,
                     }
-                    // If `owned` is None, no valid matches were found, so we
-                    // can return the original string slice
-                    // without any allocation.
-                    None => Cow::Borrowed(text),
-                }
+                })
+                .collect(),
+            Redactor::Validated(pattern, validator, replacer) => pattern
+                .find_iter(text)
+                .filter(|m| validator(m.as_str()))
+                .map(|m| Match::new(m.start(), m.end(), replacer))
+                .collect(),
+            Redactor::ReWithReplacer(pattern, replacer) => pattern
+                .find_iter(text)
+                .map(|m| Match {
+                    start: m.start(),
+                    end: m.end(),
+                    replacement: replacer(m.as_str()),
+                })
+                .collect(),
+            Redactor::ValidatedWithReplacer(pattern, validator, replacer) => {
+                pattern
+                    .find_iter(text)
+                    .filter(|m| validator(m.as_str()))
+                    .map(|m| Match {
+                        start: m.start(),
+                        end: m.end(),
+                        replacement: replacer(m.as_str()),
+                    })
+                    .collect()
             }
+            Redactor::Within(pattern, validator, inner) => pattern
+                .find_iter(text)
+                .filter(|region| validator(region.as_str()))
+                .flat_map(|region| {
+                    // Shift the inner matches back into the whole text.
+                    inner.find(region.as_str()).into_iter().map(move |m| {
+                        Match {
+                            start: region.start() + m.start,
+                            end: region.start() + m.end,
+                            replacement: m.replacement,
+                        }
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_validated_with_stateful_closure() {
+        let allowlist: HashSet<String> = HashSet::from(["ok-123".to_string()]);
+        let redactor = Redactor::validated(
+            Regex::new(r"\b[a-z]+-\d+\b").unwrap(),
+            move |candidate| !allowlist.contains(candidate),
+            None,
+        );
+        assert_eq!(redactor.redact("ok-123 and id-456"), "ok-123 and •••");
+    }
+
+    #[test]
+    fn test_severity() {
+        assert!(Severity::Secret > Severity::Identifier);
+        assert!(Severity::Identifier > Severity::QuasiIdentifier);
+        for severity in [Severity::QuasiIdentifier, Severity::Secret] {
+            assert_eq!(severity.to_string().parse(), Ok(severity));
         }
+        assert!("high".parse::<Severity>().is_err());
+
+        let named = Named::new("ip", Redactor::simple("x".to_string(), None));
+        assert_eq!(named.severity(), Severity::Secret);
+        let named = named.with_severity(Severity::QuasiIdentifier);
+        assert_eq!(named.severity(), Severity::QuasiIdentifier);
+    }
+
+    #[test]
+    fn test_from_pattern() {
+        let options = PatternOptions {
+            beep: Some("<$1>".to_string()),
+            expand_captures: true,
+            ..Default::default()
+        };
+        let redactor = Redactor::from_pattern(r"id=(\w)\w+", options).unwrap();
+        assert_eq!(redactor.redact("id=abc ID=def"), "<a> ID=def");
+
+        let err = Redactor::from_pattern("a{", PatternOptions::default());
+        assert!(matches!(err, Err(RedactorError::InvalidPattern(_))));
+    }
+
+    #[test]
+    fn test_literals_prefers_longest() {
+        let redactor =
+            Redactor::literals(["secret", "secret-token"], None).unwrap();
+        assert_eq!(
+            redactor.redact("a secret-token and a secret"),
+            "a ••• and a •••"
+        );
+    }
+
+    #[test]
+    fn test_block_requires_end() {
+        let redactor = Redactor::block("BEGIN", "END", None).unwrap();
+        assert_eq!(redactor.redact("a BEGIN b\nc END d"), "a ••• d");
+        let text = "a BEGIN b\nc\nd";
+        assert_eq!(redactor.redact(text), text);
+    }
+
+    #[test]
+    fn test_with_replacer_receives_match() {
+        let redactor = Redactor::with_replacer(
+            Regex::new(r"[a-z]+@[a-z.]+").unwrap(),
+            |m| format!("<{} bytes>", m.len()),
+        );
+        assert_eq!(
+            redactor.redact("from a@b.io to cd@ef.io"),
+            "from <6 bytes> to <8 bytes>"
+        );
     }
 }
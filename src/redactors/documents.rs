@@ -0,0 +1,316 @@
+use regex::{
+    Captures,
+    Regex,
+};
+
+use crate::redactor::Redactor;
+
+// A JSON string.
+const JSON_STRING: &str = r#""(?:[^"\\]|\\.)*""#;
+
+// A JSON string, possibly with escaped quotes, as when a document is embedded
+// in another one as a string.
+const EMBEDDED_STRING: &str = r#"\\?"(?:[^"\\]|\\[^"])*\\?""#;
+
+// The names of the (Terraform) resource attributes which are secret, e.g.
+// `password`, `master_password` and `private_key_pem`.
+const SENSITIVE_ATTRIBUTES: &str = concat!(
+    r"(?:[a-z0-9]+_)*(?:password|passphrase|private_key(?:_pem|_openssh)?",
+    r"|secret(?:_key|_string)?|access_key|api_key|token|connection_string",
+    r"|kube_config_raw)",
+);
+
+/// Redacts the values in Kubernetes `Secret` manifests, under `data` and
+/// `stringData`, keeping their keys. Manifests may be YAML or JSON, e.g. from
+/// `kubectl get secret -o yaml`, including lists of them.
+///
+/// The copy of the manifest which `kubectl apply` keeps in an annotation is
+/// redacted too.
+pub fn kubernetes_secrets_redactor() -> Option<Redactor> {
+    // A YAML document (up to a `---`) or a JSON object, with up to 2 levels of
+    // nesting (e.g. `metadata.annotations`). The last of either may not have
+    // been completely read yet.
+    let manifest = Regex::new(&format!(
+        r"(?m)^(?:apiVersion|kind):.*(?:\n(?:{}|{}|{}|{}|))*|{}",
+        r"[A-Za-z][\w.-]*:.*",
+        r"[ \t].*",
+        r"-(?:[ \t].*)?$",
+        r"#.*",
+        json_object(2),
+    ))
+    .ok()?;
+    let data = Regex::new(concat!(
+        r#"(?m)^[ \t]*(?:- )?(?:data|stringData):[ \t]*$"#,
+        r#"|"(?:data|stringData)"\s*:\s*\{"#,
+    ))
+    .ok()?;
+    let kind = Regex::new(concat!(
+        r#"(?m)^[ \t]*(?:- )?kind:[ \t]*["']?(\w+)"#,
+        r#"|"kind"\s*:\s*"(\w+)""#,
+    ))
+    .ok()?;
+    // A manifest with secret data, of which the kind is `Secret`, or isn't
+    // known (yet), e.g. as `kubectl` lists it after the data.
+    let validator = move |manifest: &str| {
+        let kinds: Vec<&str> = kind
+            .captures_iter(manifest)
+            .filter_map(|caps| caps.get(1).or(caps.get(2)))
+            .map(|kind| kind.as_str())
+            .collect();
+        (!manifest.starts_with('{') || manifest.contains("\"apiVersion\""))
+            && data.is_match(manifest)
+            && (kinds.is_empty() || kinds.contains(&"Secret"))
+    };
+
+    // The data blocks of YAML manifests, at the top level or of the items of
+    // a list, and the data objects of JSON ones (also when embedded in an
+    // annotation).
+    let blocks = Regex::new(&format!(
+        r"(?m)^{}|^{}|{}",
+        r"(?:data|stringData):[ \t]*\n(?:[ \t].*(?:\n|\z))+",
+        r"(?:- |  )(?:data|stringData):[ \t]*\n(?:   .*(?:\n|\z))+",
+        r#"\\?"(?:data|stringData)\\?"\s*:\s*\{[^{}]*(?:\}|\z)"#,
+    ))
+    .ok()?;
+    let entry = Regex::new(&format!(
+        r"(?P<key>{EMBEDDED_STRING}\s*:\s*)(?P<quote>\\?){EMBEDDED_STRING}"
+    ))
+    .ok()?;
+    let redactor = Redactor::with_replacer(blocks, move |block| {
+        if !block.starts_with(['"', '\\']) {
+            return mask_yaml_entries(block);
+        }
+        entry
+            .replace_all(block, |caps: &Captures| {
+                let quote = format!("{}\"", &caps["quote"]);
+                format!("{}{}••••⚿•{}", &caps["key"], quote, quote)
+            })
+            .into_owned()
+    });
+    Some(Redactor::within(manifest, validator, redactor))
+}
+
+/// Redacts the sensitive values in Terraform state and plan (JSON) files,
+/// keeping their structure: the values of outputs marked `"sensitive": true`,
+/// and of resource attributes with well-known sensitive names, like
+/// `password` or `private_key_pem`.
+pub fn terraform_secrets_redactor() -> Option<Redactor> {
+    let array = format!(
+        r#"\[(?:{JSON_STRING}|\[(?:{JSON_STRING}|[^\[\]"])*\]|[^\[\]"])*\]"#
+    );
+    let scalar = r"-?\d[\d.eE+-]*|true|false";
+    let value = [JSON_STRING, scalar, &json_object(1), &array].join("|");
+    let typed = format!(r#"(?:\s*"type"\s*:\s*(?:{value})\s*,)?"#);
+    let flag = "(?:true|false)";
+    let regions = [
+        // Sensitive outputs of states, planned values, and output changes.
+        format!(
+            concat!(
+                r#"\{{\s*"value"\s*:\s*(?:{value})\s*,{typed}"#,
+                r#"\s*"sensitive"\s*:\s*true\s*\}}"#,
+            ),
+            value = value,
+            typed = typed,
+        ),
+        format!(
+            concat!(
+                r#"\{{\s*"sensitive"\s*:\s*true\s*,{typed}"#,
+                r#"\s*"value"\s*:\s*(?:{value})"#,
+            ),
+            value = value,
+            typed = typed,
+        ),
+        format!(
+            concat!(
+                r#"\{{\s*"actions"\s*:\s*{array}\s*,"#,
+                r#"\s*"before"\s*:\s*(?:{value}|null)\s*,"#,
+                r#"\s*"after"\s*:\s*(?:{value}|null)\s*,"#,
+                r#"\s*"after_unknown"\s*:\s*{flag}\s*,"#,
+                r#"\s*"before_sensitive"\s*:\s*{flag}\s*,"#,
+                r#"\s*"after_sensitive"\s*:\s*true"#,
+            ),
+            array = array,
+            value = value,
+            flag = flag,
+        ),
+        // The attributes of resources, in states and plans.
+        format!(
+            r#""(?:attributes|values|before|after)"\s*:\s*{}"#,
+            json_object(3)
+        ),
+    ];
+    let regions = Regex::new(&regions.join("|")).ok()?;
+    // The values of outputs, and of sensitive attributes. Values before and
+    // after a change may be objects of attributes, which aren't masked whole.
+    let values = Regex::new(&format!(
+        r#""(?:value|{})"\s*:\s*(?:{})|"(?:before|after)"\s*:\s*(?:{}|{}|{})"#,
+        SENSITIVE_ATTRIBUTES, value, JSON_STRING, scalar, array,
+    ))
+    .ok()?;
+    let redactor = Redactor::with_replacer(values, |pair| {
+        // The keys matched have no colons.
+        let (key, value) = pair.split_once(':').unwrap_or((pair, ""));
+        let spaces = &value[..value.len() - value.trim_start().len()];
+        format!("{}:{}\"••••⚿•\"", key, spaces)
+    });
+    Some(Redactor::within(regions, |_| true, redactor))
+}
+
+// A JSON object, with up to `depth` levels of nested objects. The object may
+// not have been completely read yet, i.e. be cut short by the end of the text.
+fn json_object(depth: usize) -> String {
+    let other = r#"[^{}"]"#;
+    let object = format!(r"\{{(?:{JSON_STRING}|{other})*(?:\}}|\z)");
+    (0..depth).fold(object, |nested, _| {
+        format!(r"\{{(?:{JSON_STRING}|{nested}|{other})*(?:\}}|\z)")
+    })
+}
+
+// Masks the values of the entries of a YAML block, keeping their keys. Lines
+// nested deeper than the entries (i.e. of multi-line values) are dropped.
+fn mask_yaml_entries(block: &str) -> String {
+    let mut lines = block.split_inclusive('\n');
+    let mut masked = lines.next().unwrap_or_default().to_string();
+    let mut indent = None;
+    for line in lines {
+        let depth = line.len() - line.trim_start_matches([' ', '\t']).len();
+        if depth > *indent.get_or_insert(depth) {
+            continue;
+        }
+        match line.split_once(':') {
+            Some((key, _)) => {
+                masked.push_str(key);
+                masked.push_str(": ••••⚿•");
+                if line.ends_with('\n') {
+                    masked.push('\n');
+                }
+            }
+            None => masked.push_str(line),
+        }
+    }
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kubernetes_secrets_redactor() {
+        let redactor = kubernetes_secrets_redactor().unwrap();
+        let yaml = "\
+apiVersion: v1
+data:
+  password: aHVudGVyMg==
+  tls.key: |
+    LS0tLS1CRUdJTg==
+kind: Secret
+metadata:
+  annotations:
+    kubectl.kubernetes.io/last-applied-configuration: |
+      {\"apiVersion\":\"v1\",\"data\":{\"password\":\"aHVudGVyMg==\"}}
+  name: db
+---
+apiVersion: v1
+kind: ConfigMap
+data:
+  mode: debug
+";
+        assert_eq!(
+            redactor.redact(yaml),
+            "\
+apiVersion: v1
+data:
+  password: ••••⚿•
+  tls.key: ••••⚿•
+kind: Secret
+metadata:
+  annotations:
+    kubectl.kubernetes.io/last-applied-configuration: |
+      {\"apiVersion\":\"v1\",\"data\":{\"password\":\"••••⚿•\"}}
+  name: db
+---
+apiVersion: v1
+kind: ConfigMap
+data:
+  mode: debug
+"
+        );
+
+        let list = "apiVersion: v1\nitems:\n- apiVersion: v1\n  \
+                    stringData:\n    token: abc\n  kind: Secret\nkind: List\n";
+        assert_eq!(
+            redactor.redact(list),
+            "apiVersion: v1\nitems:\n- apiVersion: v1\n  stringData:\n    \
+             token: ••••⚿•\n  kind: Secret\nkind: List\n"
+        );
+
+        let json = r#"{
+    "apiVersion": "v1",
+    "data": {"user": "YWRtaW4=", "password": "aHVudGVyMg=="},
+    "kind": "Secret",
+    "metadata": {"annotations": {"a": "{\"data\":{\"x\":\"eQ==\"}}"}}
+}"#;
+        assert_eq!(
+            redactor.redact(json),
+            r#"{
+    "apiVersion": "v1",
+    "data": {"user": "••••⚿•", "password": "••••⚿•"},
+    "kind": "Secret",
+    "metadata": {"annotations": {"a": "{\"data\":{\"x\":\"••••⚿•\"}}"}}
+}"#
+        );
+        let other = r#"{"data": {"user": "admin"}}"#;
+        assert_eq!(redactor.redact(other), other);
+    }
+
+    #[test]
+    fn test_terraform_secrets_redactor() {
+        let redactor = terraform_secrets_redactor().unwrap();
+        let state = r#"{
+  "version": 4,
+  "outputs": {
+    "db_password": {"value": "hunter2", "type": "string", "sensitive": true},
+    "url": {"value": "https://db", "type": "string"}
+  },
+  "resources": [{"instances": [{"attributes": {
+    "engine": "postgres",
+    "master_password": "hunter2",
+    "tags": {"Name": "db"}
+  }}]}]
+}"#;
+        assert_eq!(
+            redactor.redact(state),
+            r#"{
+  "version": 4,
+  "outputs": {
+    "db_password": {"value": "••••⚿•", "type": "string", "sensitive": true},
+    "url": {"value": "https://db", "type": "string"}
+  },
+  "resources": [{"instances": [{"attributes": {
+    "engine": "postgres",
+    "master_password": "••••⚿•",
+    "tags": {"Name": "db"}
+  }}]}]
+}"#
+        );
+
+        let plan = concat!(
+            r#"{"planned_values":{"outputs":{"key":{"sensitive":true,"#,
+            r#""value":{"id":"AKIA"}}}},"output_changes":{"key":{"#,
+            r#""actions":["create"],"before":null,"after":"s3cr3t","#,
+            r#""after_unknown":false,"before_sensitive":false,"#,
+            r#""after_sensitive":true}},"resource_changes":[{"change":{"#,
+            r#""before":null,"after":{"private_key_pem":"-----BEGIN"}}}]}"#,
+        );
+        let expected = concat!(
+            r#"{"planned_values":{"outputs":{"key":{"sensitive":true,"#,
+            r#""value":"••••⚿•"}}},"output_changes":{"key":{"#,
+            r#""actions":["create"],"before":null,"after":"••••⚿•","#,
+            r#""after_unknown":false,"before_sensitive":false,"#,
+            r#""after_sensitive":true}},"resource_changes":[{"change":{"#,
+            r#""before":null,"after":{"private_key_pem":"••••⚿•"}}}]}"#,
+        );
+        assert_eq!(redactor.redact(plan), expected);
+    }
+}
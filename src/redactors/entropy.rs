@@ -0,0 +1,213 @@
+use regex::Regex;
+
+use crate::redactor::Redactor;
+use crate::redactors::RedactorOptions;
+
+// The defaults for the high entropy redactor. Random base64 strings of this
+// length have an entropy of about 4.2 bits per character, while words and
+// identifiers mostly stay below 3.5.
+const MIN_LENGTH: usize = 20;
+const MIN_ENTROPY: f64 = 4.0;
+
+// The default minimum length of base64 blobs, which is longer than most
+// identifiers and paths, but shorter than encoded keys and certificates.
+const MIN_BLOB_LENGTH: usize = 64;
+
+// Strings which are random, but usually not secret, e.g. git SHAs, digests
+// (`sha256:...`) and UUIDs.
+const BENIGN: &[&str] = &[
+    r"^[0-9a-fA-F]+$",
+    r"^[0-9a-fA-F]{8}(?:-[0-9a-fA-F]{4}){3}-[0-9a-fA-F]{12}$",
+];
+
+/// Redacts long strings which look random, by their Shannon entropy, as they
+/// are probably secrets (e.g. keys and tokens of unknown formats).
+///
+/// Strings of hex digits (like git SHAs and digests) and UUIDs are left alone.
+pub fn high_entropy_redactor() -> Option<Redactor> {
+    configured_high_entropy_redactor(&RedactorOptions::default())
+}
+
+/// Creates a `Redactor` for random looking strings, as configured by `options`
+/// (which may set the minimum length and entropy, in bits per character).
+pub fn configured_high_entropy_redactor(
+    options: &RedactorOptions,
+) -> Option<Redactor> {
+    let min_length = options.min_length.unwrap_or(MIN_LENGTH);
+    let min_entropy = options.min_entropy.unwrap_or(MIN_ENTROPY);
+    let pattern = format!(r"[A-Za-z0-9+/_-]{{{},}}={{0,2}}", min_length);
+    let regex = Regex::new(&pattern).ok()?;
+    let benign: Vec<Regex> =
+        BENIGN.iter().filter_map(|pattern| Regex::new(pattern).ok()).collect();
+    let validator = move |candidate: &str| {
+        let has_digit = candidate.bytes().any(|b| b.is_ascii_digit());
+        let has_letter = candidate.bytes().any(|b| b.is_ascii_alphabetic());
+        has_digit
+            && has_letter
+            && shannon_entropy(candidate) >= min_entropy
+            && !benign.iter().any(|re| re.is_match(candidate))
+    };
+    Some(Redactor::validated(
+        regex,
+        validator,
+        Some("••••⚿•".to_string()),
+    ))
+}
+
+/// Redacts hex tokens of the sizes of MD5, SHA-1 and SHA-256 digests (32, 40
+/// and 64 digits), as used for many secrets, except in clear git hash and
+/// digest contexts, e.g. `commit 9fceb02...` or `sha256:2cf24db...`.
+pub fn hex_tokens_redactor() -> Option<Redactor> {
+    configured_hex_tokens_redactor(&RedactorOptions::default())
+}
+
+/// Creates a `Redactor` for hex tokens, as configured by `options` (which may
+/// be aggressive, also redacting git hashes and digests).
+pub fn configured_hex_tokens_redactor(
+    options: &RedactorOptions,
+) -> Option<Redactor> {
+    let regex = Regex::new(concat!(
+        r"(?P<context>(?i:\b(?:commit|merge|tree|parent)\s+",
+        r"|\bHEAD is now at\s+|\bsha(?:1|256|512):))?",
+        r"\b(?:[0-9a-fA-F]{64}|[0-9a-fA-F]{40}|[0-9a-fA-F]{32})\b",
+    ))
+    .ok()?;
+    let aggressive = options.aggressive;
+    // Unless aggressive, only tokens without a (git) context are redacted.
+    let validator = move |token: &str| {
+        aggressive || token.bytes().all(|b| b.is_ascii_hexdigit())
+    };
+    Some(Redactor::validated_with_replacer(regex, validator, |token| {
+        let hex = token.bytes().rev().take_while(u8::is_ascii_hexdigit);
+        let context = &token[..token.len() - hex.count()];
+        format!("{}••••⚿•", context)
+    }))
+}
+
+/// Redacts long contiguous base64 blobs, which usually carry encoded
+/// credentials, keys or serialized tokens.
+pub fn base64_blobs_redactor() -> Option<Redactor> {
+    configured_base64_blobs_redactor(&RedactorOptions::default())
+}
+
+/// Creates a `Redactor` for base64 blobs, as configured by `options` (which
+/// may set their minimum length, without the padding).
+pub fn configured_base64_blobs_redactor(
+    options: &RedactorOptions,
+) -> Option<Redactor> {
+    let min_length = options.min_length.unwrap_or(MIN_BLOB_LENGTH);
+    let pattern = format!(r"[A-Za-z0-9+/]{{{},}}={{0,2}}", min_length);
+    let regex = Regex::new(&pattern).ok()?;
+    // Encoded data has digits and letters of both cases, unlike long paths or
+    // words.
+    let validator = |blob: &str| {
+        blob.bytes().any(|b| b.is_ascii_digit())
+            && blob.bytes().any(|b| b.is_ascii_lowercase())
+            && blob.bytes().any(|b| b.is_ascii_uppercase())
+    };
+    Some(Redactor::validated(
+        regex,
+        validator,
+        Some("••••⚿•".to_string()),
+    ))
+}
+
+// The Shannon entropy of `text`, in bits per character (byte).
+pub(crate) fn shannon_entropy(text: &str) -> f64 {
+    let mut counts = [0usize; 256];
+    for byte in text.bytes() {
+        counts[byte as usize] += 1;
+    }
+    let len = text.len() as f64;
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shannon_entropy() {
+        assert_eq!(shannon_entropy("aaaa"), 0.0);
+        assert_eq!(shannon_entropy("abab"), 1.0);
+        assert_eq!(shannon_entropy("abcd"), 2.0);
+    }
+
+    #[test]
+    fn test_hex_tokens_redactor() {
+        let sha = "9fceb02d0ae598e95dc970b74767f19372d61af8";
+        let redactor = hex_tokens_redactor().unwrap();
+        assert_eq!(
+            redactor.redact("api_key=0123456789abcdef0123456789abcdef"),
+            "api_key=••••⚿•"
+        );
+        for context in ["commit ", "HEAD is now at ", "sha1:"] {
+            let text = format!("{}{}", context, sha);
+            assert_eq!(redactor.redact(&text), text);
+        }
+        assert_eq!(redactor.redact(&format!("{}0", sha)), format!("{}0", sha));
+
+        let aggressive = configured_hex_tokens_redactor(&RedactorOptions {
+            aggressive: true,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            aggressive.redact(&format!("commit {}", sha)),
+            "commit ••••⚿•"
+        );
+    }
+
+    #[test]
+    fn test_base64_blobs_redactor() {
+        let blob = "eyJ1c2VyIjoiYWRtaW4iLCJwYXNzd29yZCI6Imh1bnRlcjIifQ";
+        let redactor = base64_blobs_redactor().unwrap();
+        let text = format!("payload: {}{}==", blob, blob);
+        assert_eq!(redactor.redact(&text), "payload: ••••⚿•");
+        let path = "/usr/share/doc/".repeat(5);
+        assert_eq!(redactor.redact(&path), path);
+        assert_eq!(redactor.redact(blob), blob);
+
+        let short = configured_base64_blobs_redactor(&RedactorOptions {
+            min_length: Some(32),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(short.redact(&format!("data={}", blob)), "data=••••⚿•");
+    }
+
+    #[test]
+    fn test_high_entropy_redactor() {
+        let redactor = high_entropy_redactor().unwrap();
+        assert_eq!(
+            redactor.redact("token: q8Zr3LpX0vT7mW2yK9bN4cJ6"),
+            "token: ••••⚿•"
+        );
+        let digest = format!("sha256:{}", "2cf24dba5fb0a30e".repeat(4));
+        let benign = [
+            "commit 9fceb02d0ae598e95dc970b74767f19372d61af8",
+            &digest,
+            "at /usr/lib/x86_64-linux-gnu/libc.so.6",
+            "ThisIsAVeryLongClassNameWithoutDigits",
+            "id 123e4567-e89b-12d3-a456-426614174000",
+        ];
+        for text in benign {
+            assert_eq!(redactor.redact(text), text);
+        }
+
+        let short = configured_high_entropy_redactor(&RedactorOptions {
+            min_length: Some(12),
+            min_entropy: Some(3.5),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(short.redact("pw=Xk29vLq8Tz1w"), "pw=••••⚿•");
+    }
+}
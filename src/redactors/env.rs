@@ -1,44 +1,79 @@
 use std::env;
 
-use regex::{
-    Regex,
-    RegexBuilder,
-};
+use regex::RegexBuilder;
 
-use crate::redactor::Redactor;
+use crate::error::RedactorError;
+use crate::redactor::{
+    PatternOptions,
+    Redactor,
+};
 
 const ENV_SECRET_PATTERNS: &[&str] =
     &["password", "secret", "token", "key", "username", "email"];
-const MIN_SECRET_LENGTH: usize = 5;
+pub(crate) const MIN_SECRET_LENGTH: usize = 5;
+
+/// The environment variable listing built-in redactors to disable, e.g.
+/// `BIIP_DISABLE=uuid,ipv6`.
+pub const DISABLE_VAR: &str = "BIIP_DISABLE";
+
+/// The environment variable setting the style of the replacements for the
+/// command line, e.g. `BIIP_STYLE=labels`.
+pub const STYLE_VAR: &str = "BIIP_STYLE";
+
+/// The environment variable setting the minimum severity of the values to
+/// redact for the command line, e.g. `BIIP_MIN_SEVERITY=secret`.
+pub const MIN_SEVERITY_VAR: &str = "BIIP_MIN_SEVERITY";
+
+// The variables configuring biip itself, rather than holding patterns.
+const SETTINGS_VARS: &[&str] = &[DISABLE_VAR, STYLE_VAR, MIN_SEVERITY_VAR];
+
+/// Returns the names of the redactors disabled with the `BIIP_DISABLE`
+/// environment variable.
+pub fn disabled_redactors() -> Vec<String> {
+    env::var(DISABLE_VAR)
+        .map(|value| parse_names(&value))
+        .unwrap_or_default()
+}
+
+// Parses a comma (or whitespace) separated list of redactor names.
+pub(crate) fn parse_names(value: &str) -> Vec<String> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
 
 /// Creates a `Redactor` for sensitive environment variables.
 ///
-/// This function scans all environment variables and creates a regex pattern
-/// to match the values of variables whose keys contain sensitive keywords
-/// (e.g., "password", "secret", "token", "key").
+/// This function scans all environment variables and creates a matcher for
+/// the values of variables whose keys contain sensitive keywords (e.g.,
+/// "password", "secret", "token", "key").
 ///
 /// The matched values are replaced with `••••⚿•`.
 ///
 /// Returns `None` if no such environment variables are found.
 pub fn secrets_redactor() -> Option<Redactor> {
-    let env_vars: Vec<String> = env::vars()
+    try_secrets_redactor().ok().flatten()
+}
+
+/// Like [`secrets_redactor`], but fails if the matcher for the secrets
+/// cannot be built.
+pub fn try_secrets_redactor() -> Result<Option<Redactor>, RedactorError> {
+    let secrets: Vec<String> = env::vars()
         .filter(|(key, value)| {
             ENV_SECRET_PATTERNS
                 .iter()
                 .any(|pattern| key.to_lowercase().contains(pattern))
                 && value.trim().len() > MIN_SECRET_LENGTH
         })
-        .map(|(_, value)| regex::escape(value.trim()))
+        .map(|(_, value)| value.trim().to_string())
         .collect();
-    let pattern = env_vars.join("|");
-
-    if pattern.is_empty() {
-        None
-    } else {
-        Regex::new(&pattern)
-            .ok()
-            .map(|regex| Redactor::regex(regex, Some(String::from("••••⚿•"))))
+
+    if secrets.is_empty() {
+        return Ok(None);
     }
+    Redactor::literals(secrets, Some(String::from("••••⚿•"))).map(Some)
 }
 
 /// Creates a `Redactor` for any environment variables whose names start with
@@ -49,15 +84,7 @@ pub fn secrets_redactor() -> Option<Redactor> {
 ///
 /// Returns `None` if no such environment variables are found.
 pub fn custom_patterns_redactor() -> Option<Redactor> {
-    // Collect raw regex patterns from BIIP_* env vars (case-insensitive
-    // matching)
-    let raw_patterns: Vec<String> = env::vars()
-        .filter(|(key, value)| {
-            key.to_uppercase().starts_with("BIIP") && !value.trim().is_empty()
-        })
-        .map(|(_, value)| value.trim().to_string())
-        .collect();
-
+    let raw_patterns = custom_patterns();
     if raw_patterns.is_empty() {
         return None;
     }
@@ -84,8 +111,8 @@ pub fn custom_patterns_redactor() -> Option<Redactor> {
     }
 
     let combined = format!("(?:{})", valid_parts.join("|"));
-    match RegexBuilder::new(&combined).case_insensitive(true).build() {
-        Ok(re) => Some(Redactor::regex(re, Some(String::from("••••⚙•")))),
+    match Redactor::from_pattern(&combined, custom_pattern_options()) {
+        Ok(redactor) => Some(redactor),
         Err(err) => {
             eprintln!(
                 "[biip] Warning: failed to build combined BIIP_* regex: {}",
@@ -96,10 +123,58 @@ pub fn custom_patterns_redactor() -> Option<Redactor> {
     }
 }
 
+/// Like [`custom_patterns_redactor`], but fails on the first invalid pattern
+/// instead of skipping it.
+pub fn try_custom_patterns_redactor()
+-> Result<Option<Redactor>, RedactorError> {
+    let raw_patterns = custom_patterns();
+    if raw_patterns.is_empty() {
+        return Ok(None);
+    }
+    for pattern in &raw_patterns {
+        RegexBuilder::new(pattern).case_insensitive(true).build()?;
+    }
+
+    let combined = format!("(?:{})", raw_patterns.join("|"));
+    Redactor::from_pattern(&combined, custom_pattern_options()).map(Some)
+}
+
+fn custom_pattern_options() -> PatternOptions {
+    PatternOptions {
+        beep: Some(String::from("••••⚙•")),
+        case_insensitive: true,
+        ..Default::default()
+    }
+}
+
+// Collect raw regex patterns from BIIP_* env vars (case-insensitive matching).
+// Variables configuring biip itself, like `BIIP_DISABLE`, are not patterns.
+fn custom_patterns() -> Vec<String> {
+    env::vars()
+        .filter(|(key, value)| {
+            let key = key.to_uppercase();
+            key.starts_with("BIIP")
+                && !SETTINGS_VARS.contains(&key.as_str())
+                && !value.trim().is_empty()
+        })
+        .map(|(_, value)| value.trim().to_string())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_names() {
+        assert_eq!(parse_names("phone,uuid, credit_card"), [
+            "phone",
+            "uuid",
+            "credit_card"
+        ]);
+        assert!(parse_names(" , ").is_empty());
+    }
+
     #[test]
     fn test_secrets_redactor() {
         unsafe {
@@ -169,5 +244,11 @@ mod tests {
 
         let redactor = custom_patterns_redactor().unwrap();
         assert_eq!(redactor.redact("X Qux Y"), "X ••••⚙• Y");
+
+        // The strict variant refuses to silently drop the invalid pattern.
+        assert!(matches!(
+            try_custom_patterns_redactor(),
+            Err(RedactorError::InvalidPattern(_))
+        ));
     }
 }
@@ -0,0 +1,97 @@
+use regex::Regex;
+
+use crate::redactor::Redactor;
+
+/// Redacts SWIFT/BIC codes (8 or 11 characters, e.g. `DEUTDEFF500`) after a
+/// `SWIFT` or `BIC` keyword, which is kept. Codes without such a keyword can't
+/// be told apart from ordinary uppercase words.
+pub fn bic_redactor() -> Option<Redactor> {
+    Regex::new(concat!(
+        r#"(?P<context>\b(?i:swift|bic)(?i:[ /_-]?(?:bic|code|no\.?|number))?"#,
+        r#"["']?[ \t]*[:=#]?[ \t]*["']?)"#,
+        r"[A-Z]{4}[A-Z]{2}[A-Z0-9]{2}(?:[A-Z0-9]{3})?\b",
+    ))
+    .ok()
+    .map(|re| Redactor::regex_with_capture(re, "${context}••••••••".into()))
+}
+
+/// Redacts US bank (ABA) routing numbers: 9 digits, starting with a valid
+/// Federal Reserve prefix, which pass the ABA checksum.
+pub fn routing_number_redactor() -> Option<Redactor> {
+    Regex::new(r"\b(?:0[0-9]|1[0-2]|2[1-9]|3[0-2]|6[1-9]|7[0-2]|80)\d{7}\b")
+        .ok()
+        .map(|re| {
+            Redactor::validated(
+                re,
+                is_aba_valid,
+                Some("•••••••••".to_string()),
+            )
+        })
+}
+
+/// Redacts bank account numbers after a keyword like `account number:` or
+/// `acct #`, which is kept.
+pub fn account_number_redactor() -> Option<Redactor> {
+    Regex::new(concat!(
+        r"(?P<context>\b(?i:(?:bank[ _-]?)?account[ _-]?(?:number|no\.?|#)",
+        r"|acct\.?[ _-]?(?:number|no\.?|#)?)[ \t]*[:=#]?[ \t]*)",
+        r"\d[\d -]{2,15}\d\b",
+    ))
+    .ok()
+    .map(|re| Redactor::regex_with_capture(re, "${context}••••••".into()))
+}
+
+// Whether a routing number passes the ABA checksum, with the weights 3, 7
+// and 1.
+fn is_aba_valid(number: &str) -> bool {
+    let sum: u32 = number
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .zip([3, 7, 1].iter().cycle())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bic_redactor() {
+        let redactor = bic_redactor().unwrap();
+        assert_eq!(redactor.redact("SWIFT: DEUTDEFF"), "SWIFT: ••••••••");
+        assert_eq!(
+            redactor.redact("SWIFT/BIC code DEUTDEFF500, done"),
+            "SWIFT/BIC code ••••••••, done"
+        );
+        assert_eq!(
+            redactor.redact(r#"{"bic": "BNPAFRPP"}"#),
+            r#"{"bic": "••••••••"}"#
+        );
+        for text in ["DEUTDEFF", "swift: EXCELLENT", "BIC: ABCD"] {
+            assert_eq!(redactor.redact(text), text);
+        }
+    }
+
+    #[test]
+    fn test_routing_number_redactor() {
+        let redactor = routing_number_redactor().unwrap();
+        assert_eq!(redactor.redact("ABA 021000021"), "ABA •••••••••");
+        // Fails the checksum, or has an invalid prefix.
+        for text in ["021000022", "991000021"] {
+            assert_eq!(redactor.redact(text), text);
+        }
+    }
+
+    #[test]
+    fn test_account_number_redactor() {
+        let redactor = account_number_redactor().unwrap();
+        assert_eq!(
+            redactor.redact("Account number: 000123456789."),
+            "Account number: ••••••."
+        );
+        assert_eq!(redactor.redact("acct #12-3456-78"), "acct #••••••");
+        assert_eq!(redactor.redact("account 42 of 50"), "account 42 of 50");
+    }
+}
@@ -0,0 +1,83 @@
+use regex::Regex;
+
+use crate::redactor::Redactor;
+use crate::redactors::patterns::is_luhn_valid;
+
+/// Redacts medical record numbers (MRNs) after a keyword like `MRN:` or
+/// `medical record number`, which is kept. MRNs are assigned by each
+/// provider, so they can't be told apart from other numbers without the
+/// keyword.
+pub fn mrn_redactor() -> Option<Redactor> {
+    Regex::new(concat!(
+        r"(?P<context>\b(?i:mrn|medical[ _-]?record",
+        r#"(?:[ _-]?(?:number|no\.?|#))?)["']?[ \t]*[:=#]?[ \t]*["']?)"#,
+        r"[A-Z]{0,3}-?\d[\d-]{4,12}\d\b",
+    ))
+    .ok()
+    .map(|re| Redactor::regex_with_capture(re, "${context}••••••••".into()))
+}
+
+/// Redacts US National Provider Identifiers (NPIs): 10 digits, starting with
+/// 1 or 2, with a valid (Luhn) check digit.
+pub fn npi_redactor() -> Option<Redactor> {
+    Regex::new(r"\b[12]\d{9}\b").ok().map(|re| {
+        Redactor::validated(
+            re,
+            // The check digit covers the `80840` prefix of health
+            // identifiers too.
+            |npi| is_luhn_valid(&format!("80840{npi}")),
+            Some("••••••••••".to_string()),
+        )
+    })
+}
+
+/// Redacts US DEA registration numbers, e.g. `AB1234563`, with a valid check
+/// digit.
+pub fn dea_redactor() -> Option<Redactor> {
+    Regex::new(r"\b[ABCDEFGHJKLMPRSTUX][A-Z9]\d{7}\b").ok().map(|re| {
+        Redactor::validated(re, is_dea_valid, Some("•••••••••".to_string()))
+    })
+}
+
+// Whether the last digit of a DEA number matches the sum of its 1st, 3rd and
+// 5th digits, plus twice the sum of its 2nd, 4th and 6th digits.
+fn is_dea_valid(number: &str) -> bool {
+    let digits: Vec<u32> =
+        number.chars().filter_map(|c| c.to_digit(10)).collect();
+    let [d1, d2, d3, d4, d5, d6, check] = digits[digits.len() - 7..] else {
+        return false;
+    };
+    (d1 + d3 + d5 + 2 * (d2 + d4 + d6)) % 10 == check
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mrn_redactor() {
+        let redactor = mrn_redactor().unwrap();
+        assert_eq!(redactor.redact("MRN: 00123456"), "MRN: ••••••••");
+        assert_eq!(
+            redactor.redact(r#"{"medical_record_number": "MR-2024-0042"}"#),
+            r#"{"medical_record_number": "••••••••"}"#
+        );
+        for text in ["00123456", "MRN: pending"] {
+            assert_eq!(redactor.redact(text), text);
+        }
+    }
+
+    #[test]
+    fn test_npi_redactor() {
+        let redactor = npi_redactor().unwrap();
+        assert_eq!(redactor.redact("NPI 1234567893."), "NPI ••••••••••.");
+        assert_eq!(redactor.redact("1234567890"), "1234567890");
+    }
+
+    #[test]
+    fn test_dea_redactor() {
+        let redactor = dea_redactor().unwrap();
+        assert_eq!(redactor.redact("DEA: AB1234563"), "DEA: •••••••••");
+        assert_eq!(redactor.redact("AB1234564"), "AB1234564");
+    }
+}
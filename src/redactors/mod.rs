@@ -1,37 +1,571 @@
 //! This module contains the various redactors used by `biip`.
 //!
 //! Each submodule is responsible for a specific category of redactions.
+pub mod credentials;
+pub mod documents;
+pub mod entropy;
 pub mod env;
+pub mod finance;
+pub mod medical;
+#[cfg(feature = "names")]
+pub mod names;
 pub mod network;
 pub mod patterns;
+pub mod regional;
+pub mod secrets_file;
 pub mod user;
 
+use crate::error::RedactorError;
+use crate::redactor::{
+    Redactor,
+    Severity,
+};
+
+/// Redacts the secrets in the credential files of common tools.
+/// @see credentials
+pub use credentials::{
+    configured_credential_files_redactor,
+    credential_files_redactor,
+    try_credential_files_redactor,
+};
+/// Redacts the secrets in structured documents, like Kubernetes manifests and
+/// Terraform states.
+/// @see documents
+pub use documents::{
+    kubernetes_secrets_redactor,
+    terraform_secrets_redactor,
+};
+/// Redacts strings which look random, like hex tokens, as probable secrets.
+/// @see entropy
+pub use entropy::{
+    base64_blobs_redactor,
+    configured_base64_blobs_redactor,
+    configured_hex_tokens_redactor,
+    configured_high_entropy_redactor,
+    hex_tokens_redactor,
+    high_entropy_redactor,
+};
 /// Redacts sensitive information from environment variables.
 /// @see env::{secrets_redactor, custom_patterns_redactor}
 pub use env::{
     custom_patterns_redactor,
+    disabled_redactors,
     secrets_redactor,
+    try_custom_patterns_redactor,
+    try_secrets_redactor,
+};
+/// Redacts financial identifiers, like SWIFT/BIC codes.
+/// @see finance
+pub use finance::{
+    account_number_redactor,
+    bic_redactor,
+    routing_number_redactor,
+};
+/// Redacts healthcare identifiers, like medical record numbers.
+/// @see medical
+pub use medical::{
+    dea_redactor,
+    mrn_redactor,
+    npi_redactor,
+};
+/// Redacts person names, by wordlists of common names.
+/// @see names
+#[cfg(feature = "names")]
+pub use names::{
+    configured_person_names_redactor,
+    person_names_redactor,
 };
 /// Redacts networking patterns like email addresses and IP addresses.
 /// @see network
 pub use network::{
+    configured_email_redactor,
+    configured_ipv4_redactor,
+    configured_ipv6_redactor,
+    configured_mac_address_redactor,
+    cookies_redactor,
     email_redactor,
     ipv4_redactor,
     ipv6_redactor,
     mac_address_redactor,
+    partial_email_redactor,
+    partial_ipv4_redactor,
+    partial_ipv6_redactor,
+    prefix_preserving_ipv4_redactor,
+    prefix_preserving_ipv6_redactor,
+    query_params_redactor,
     url_credentials_redactor,
 };
 // Redact sensitive information which follow a specific pattern.
 pub use patterns::{
+    azure_credentials_redactor,
+    bot_tokens_redactor,
+    certificates_redactor,
     cloud_keys_redactor,
+    coordinates_redactor,
+    configured_credit_card_redactor,
+    configured_phone_number_redactor,
+    configured_stripe_keys_redactor,
+    configured_uuid_redactor,
     credit_card_redactor,
+    date_of_birth_redactor,
+    gcp_service_account_redactor,
+    gitlab_tokens_redactor,
+    imei_redactor,
     jwt_redactor,
+    paas_tokens_redactor,
+    partial_credit_card_redactor,
+    passport_redactor,
+    pgp_blocks_redactor,
     phone_number_redactor,
+    postal_code_redactor,
+    private_keys_redactor,
+    stripe_keys_redactor,
     uuid_redactor,
+    vin_redactor,
+};
+/// Redacts identifiers used in some regions only, like Indian Aadhaar numbers.
+/// @see regional
+pub use regional::{
+    aadhaar_redactor,
+    cnpj_redactor,
+    configured_driver_license_redactor,
+    configured_license_plate_redactor,
+    cpf_redactor,
+    driver_license_redactor,
+    eu_vat_redactor,
+    license_plate_redactor,
+    nir_redactor,
+    pan_redactor,
+    sin_redactor,
+    steuer_id_redactor,
 };
-/// Redacts user-specific information like home directory and username.
+/// Redacts the values listed in `.biip-secrets` files.
+/// @see secrets_file
+pub use secrets_file::{
+    secrets_file_redactor,
+    try_secrets_file_redactor,
+};
+/// Redacts user-specific information like home directory, username and
+/// hostname.
 /// @see user
 pub use user::{
+    configured_other_homes_redactor,
+    configured_wifi_ssids_redactor,
+    git_identity_redactor,
     home_redactor,
+    hostname_redactor,
+    identity_env_redactor,
+    other_homes_redactor,
+    ssh_hosts_redactor,
+    try_home_redactor,
+    try_username_redactor,
     username_redactor,
+    wifi_ssids_redactor,
 };
+
+// The icons which, besides `•`, appear in the replacements of the built-in
+// redactors (e.g. `⚿` in `••••⚿•` for secrets), and the variation selector of
+// emoji icons (e.g. `☁️`). Styles mask them like `•`.
+pub(crate) const MARKER_ICONS: &[char] =
+    &['⚿', '⚙', '🌐', '☁', '🦊', '🤖', '💳', '📜', '\u{FE0F}'];
+
+/// A function which constructs a redactor, returning `None` if it is not
+/// applicable (e.g. the environment has nothing for it to redact).
+pub type Factory = fn() -> Option<Redactor>;
+
+/// A fallible counterpart to [`Factory`]. `Ok(None)` means that the redactor
+/// is not applicable, while an error means that it should have been built but
+/// could not be.
+pub type TryFactory = fn() -> Result<Option<Redactor>, RedactorError>;
+
+/// A [`Factory`] for redactors which support [`RedactorOptions`].
+pub type ConfiguredFactory = fn(&RedactorOptions) -> Option<Redactor>;
+
+/// Options for the built-in redactors which support them (see
+/// [`Definition::configurable`]). Redactors ignore the options they don't
+/// support.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct RedactorOptions {
+    /// Only mask part of each value: emails keep their domain, IP addresses
+    /// their first octet or group, and MAC addresses their vendor (OUI).
+    pub partial: bool,
+    /// Keep the domain of emails, e.g. `•••@example.com`.
+    pub keep_domain: bool,
+    /// Keep this many leading bits of IP addresses, rounded down to whole
+    /// octets (IPv4) or groups (IPv6), e.g. `8.8.8.••` for 24.
+    pub keep_prefix: Option<u8>,
+    /// Keep the vendor part (OUI) of MAC addresses, e.g. `00:1a:2b:••:••:••`.
+    pub keep_oui: bool,
+    /// Replace values with keyed pseudonyms preserving their prefixes, e.g.
+    /// so that IP addresses in the same subnet stay in the same subnet.
+    pub prefix_preserving: bool,
+    /// Also redact values which are usually not sensitive, e.g. private and
+    /// link-local IP addresses.
+    pub aggressive: bool,
+    /// Also redact hostnames, e.g. of the Kubernetes clusters in kubeconfig
+    /// files.
+    pub hostnames: bool,
+    /// Also redact keys for test environments, e.g. Stripe's `sk_test_` keys.
+    pub test_keys: bool,
+    /// Skip payment card numbers following the keywords of other identifiers,
+    /// e.g. `order #` or `tracking number`.
+    pub skip_ids: bool,
+    /// Skip nil UUIDs, i.e. `00000000-0000-0000-0000-000000000000` (and the
+    /// max UUID, of `f`s).
+    pub skip_nil: bool,
+    /// Values to leave alone, e.g. well-known UUIDs, in any case.
+    pub skip: Vec<String>,
+    /// Only redact values following identity keywords, e.g. `user_id=` or
+    /// `account:`.
+    pub identity_only: bool,
+    /// The US states (e.g. `CA`) whose formats of driver's license numbers to
+    /// redact, or all of them if empty.
+    pub states: Vec<String>,
+    /// The countries or US states (e.g. `gb` or `us-ca`) whose formats of
+    /// license plates to redact, or all of them if empty.
+    pub locales: Vec<String>,
+    /// Only redact the home directories of the users in `/etc/passwd`.
+    pub known_users: bool,
+    /// The names (SSIDs) of Wi-Fi networks to redact, besides the ones known
+    /// to this machine.
+    pub ssids: Vec<String>,
+    /// Names to redact as person names, besides the bundled ones.
+    pub names: Vec<String>,
+    /// The minimum length of the values to redact, for redactors of values
+    /// without a fixed length (e.g. random looking strings and base64 blobs).
+    pub min_length: Option<usize>,
+    /// The minimum Shannon entropy of random looking strings to redact, in
+    /// bits per character.
+    pub min_entropy: Option<f64>,
+    /// The key for pseudonyms.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub key: Vec<u8>,
+}
+
+/// A coarse grouping of redactors, by the kind of information they redact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Category {
+    /// Addresses and credentials seen on networks, e.g. IPs and emails.
+    Network,
+    /// Keys and tokens for cloud services and APIs.
+    Cloud,
+    /// Information identifying a person or machine, e.g. usernames.
+    Identity,
+    /// Financial information, e.g. payment card numbers.
+    Finance,
+    /// Values and patterns configured by the user, e.g. via `BIIP_*`.
+    Custom,
+}
+
+/// The definition of a built-in redactor.
+pub struct Definition {
+    /// The name of the redactor, e.g. `email`.
+    pub name: &'static str,
+    /// The category of the redactor.
+    pub category: Category,
+    /// How sensitive the values the redactor finds are.
+    pub severity: Severity,
+    /// Whether the redactor is only used when enabled by name (see
+    /// [`Definition::opt_in`]).
+    pub opt_in: bool,
+    /// The region of the identifiers the redactor finds, if they are regional
+    /// (see [`Definition::region`]).
+    pub region: Option<&'static str>,
+    build: Factory,
+    try_build: Option<TryFactory>,
+    pub(crate) configured: Option<ConfiguredFactory>,
+}
+
+impl Definition {
+    /// Creates a definition for a redactor which should always be available,
+    /// finding identifiers (see [`Definition::severity`]).
+    pub const fn new(
+        name: &'static str,
+        category: Category,
+        build: Factory,
+    ) -> Self {
+        Definition {
+            name,
+            category,
+            severity: Severity::Identifier,
+            opt_in: false,
+            region: None,
+            build,
+            try_build: None,
+            configured: None,
+        }
+    }
+
+    /// Sets the fallible constructor, for redactors which depend on the
+    /// environment.
+    pub const fn fallible(mut self, try_build: TryFactory) -> Self {
+        self.try_build = Some(try_build);
+        self
+    }
+
+    /// Sets how sensitive the values the redactor finds are.
+    pub const fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Makes the redactor opt-in, for values which are often fine to share:
+    /// it is left out of the defaults, unless enabled by name (see
+    /// [`BiipBuilder::enable`](crate::BiipBuilder::enable)).
+    pub const fn opt_in(mut self) -> Self {
+        self.opt_in = true;
+        self
+    }
+
+    /// Makes the redactor regional, for identifiers used in a region (e.g.
+    /// `in` for India) which would be false positives elsewhere. Regional
+    /// redactors are opt-in, and also enabled with their region (see
+    /// [`BiipBuilder::with_region`](crate::BiipBuilder::with_region)).
+    pub const fn region(mut self, region: &'static str) -> Self {
+        self.region = Some(region);
+        self.opt_in = true;
+        self
+    }
+
+    /// Sets the constructor of the redactor with [`RedactorOptions`], for
+    /// redactors which support them.
+    pub const fn configurable(mut self, configured: ConfiguredFactory) -> Self {
+        self.configured = Some(configured);
+        self
+    }
+
+    /// Builds the redactor, returning `None` if it is not available.
+    pub fn build(&self) -> Option<Redactor> {
+        (self.build)()
+    }
+
+    /// Builds the redactor, failing if it should have been built but could not
+    /// be.
+    pub fn try_build(&self) -> Result<Option<Redactor>, RedactorError> {
+        match self.try_build {
+            Some(try_build) => try_build(),
+            None => self.build().map(Some).ok_or(RedactorError::Unavailable),
+        }
+    }
+
+}
+
+/// Returns the names of the regional redactors for `region` (e.g. `in`), in
+/// any case.
+pub fn regional_names(region: &str) -> impl Iterator<Item = &'static str> {
+    DEFAULTS
+        .iter()
+        .filter(move |definition| {
+            definition
+                .region
+                .is_some_and(|name| name.eq_ignore_ascii_case(region))
+        })
+        .map(|definition| definition.name)
+}
+
+/// The default redactors, in the order they are applied.
+///
+/// The order is important to prevent conflicts (e.g., a MAC address being
+/// mistaken for a partial IPv6 address). The order is generally:
+/// 1. User and environment-specific (most specific).
+/// 2. Networking patterns with specific formats.
+/// 3. Generic patterns like JWTs and UUIDs.
+pub const DEFAULTS: &[Definition] = &[
+    // User-specific redactors
+    Definition::new("home", Category::Identity, home_redactor)
+        .fallible(try_home_redactor),
+    Definition::new("username", Category::Identity, username_redactor)
+        .fallible(try_username_redactor),
+    Definition::new("other_homes", Category::Identity, other_homes_redactor)
+        .configurable(configured_other_homes_redactor),
+    Definition::new("hostname", Category::Identity, hostname_redactor),
+    Definition::new(
+        "git_identity",
+        Category::Identity,
+        git_identity_redactor,
+    ),
+    Definition::new(
+        "identity_env",
+        Category::Identity,
+        identity_env_redactor,
+    ),
+    Definition::new("ssh_hosts", Category::Network, ssh_hosts_redactor)
+        .opt_in(),
+    Definition::new("wifi_ssids", Category::Identity, wifi_ssids_redactor)
+        .configurable(configured_wifi_ssids_redactor)
+        .opt_in(),
+    // Environment and secrets
+    Definition::new("secrets", Category::Custom, secrets_redactor)
+        .fallible(try_secrets_redactor)
+        .severity(Severity::Secret),
+    Definition::new("secrets_file", Category::Custom, secrets_file_redactor)
+        .fallible(try_secrets_file_redactor)
+        .severity(Severity::Secret),
+    Definition::new(
+        "credential_files",
+        Category::Custom,
+        credential_files_redactor,
+    )
+    .fallible(try_credential_files_redactor)
+    .configurable(configured_credential_files_redactor)
+    .severity(Severity::Secret)
+    .opt_in(),
+    Definition::new(
+        "custom_patterns",
+        Category::Custom,
+        custom_patterns_redactor,
+    )
+    .fallible(try_custom_patterns_redactor)
+    .severity(Severity::Secret),
+    // Structured secrets, before the patterns within them (e.g. emails)
+    Definition::new(
+        "kubernetes_secrets",
+        Category::Cloud,
+        kubernetes_secrets_redactor,
+    )
+    .severity(Severity::Secret),
+    Definition::new(
+        "terraform_secrets",
+        Category::Cloud,
+        terraform_secrets_redactor,
+    )
+    .severity(Severity::Secret),
+    Definition::new(
+        "gcp_service_account",
+        Category::Cloud,
+        gcp_service_account_redactor,
+    )
+    .severity(Severity::Secret),
+    Definition::new("private_keys", Category::Cloud, private_keys_redactor)
+        .severity(Severity::Secret),
+    Definition::new("pgp_blocks", Category::Cloud, pgp_blocks_redactor)
+        .severity(Severity::Secret),
+    Definition::new("certificates", Category::Identity, certificates_redactor)
+        .opt_in(),
+    // Networking patterns (order is important here)
+    Definition::new("cookies", Category::Network, cookies_redactor)
+        .severity(Severity::Secret),
+    Definition::new("query_params", Category::Network, query_params_redactor)
+        .severity(Severity::Secret),
+    Definition::new(
+        "url_credentials",
+        Category::Network,
+        url_credentials_redactor,
+    )
+    .severity(Severity::Secret),
+    Definition::new("email", Category::Network, email_redactor)
+        .configurable(configured_email_redactor),
+    Definition::new("mac_address", Category::Network, mac_address_redactor)
+        .configurable(configured_mac_address_redactor),
+    Definition::new("ipv4", Category::Network, ipv4_redactor)
+        .configurable(configured_ipv4_redactor)
+        .severity(Severity::QuasiIdentifier),
+    Definition::new("ipv6", Category::Network, ipv6_redactor)
+        .configurable(configured_ipv6_redactor)
+        .severity(Severity::QuasiIdentifier),
+    // Generic and vendor-specific patterns
+    Definition::new("jwt", Category::Cloud, jwt_redactor)
+        .severity(Severity::Secret),
+    // Before `uuid`, as some tokens are UUIDs.
+    Definition::new("paas_tokens", Category::Cloud, paas_tokens_redactor)
+        .severity(Severity::Secret),
+    Definition::new("uuid", Category::Identity, uuid_redactor)
+        .configurable(configured_uuid_redactor)
+        .severity(Severity::QuasiIdentifier),
+    Definition::new("imei", Category::Identity, imei_redactor),
+    Definition::new("vin", Category::Identity, vin_redactor),
+    Definition::new("passport", Category::Identity, passport_redactor),
+    Definition::new("postal_code", Category::Identity, postal_code_redactor)
+        .severity(Severity::QuasiIdentifier),
+    Definition::new("coordinates", Category::Identity, coordinates_redactor),
+    Definition::new("phone_number", Category::Identity, phone_number_redactor)
+        .configurable(configured_phone_number_redactor)
+        .opt_in(),
+    Definition::new(
+        "date_of_birth",
+        Category::Identity,
+        date_of_birth_redactor,
+    ),
+    #[cfg(feature = "names")]
+    Definition::new("person_names", Category::Identity, person_names_redactor)
+        .configurable(configured_person_names_redactor)
+        .opt_in(),
+    Definition::new("cloud_keys", Category::Cloud, cloud_keys_redactor)
+        .severity(Severity::Secret),
+    Definition::new("gitlab_tokens", Category::Cloud, gitlab_tokens_redactor)
+        .severity(Severity::Secret),
+    Definition::new("stripe_keys", Category::Cloud, stripe_keys_redactor)
+        .configurable(configured_stripe_keys_redactor)
+        .severity(Severity::Secret),
+    Definition::new("bot_tokens", Category::Cloud, bot_tokens_redactor)
+        .severity(Severity::Secret),
+    Definition::new(
+        "azure_credentials",
+        Category::Cloud,
+        azure_credentials_redactor,
+    )
+    .severity(Severity::Secret),
+    // Financial identifiers
+    Definition::new("credit_card", Category::Finance, credit_card_redactor)
+        .configurable(configured_credit_card_redactor)
+        .opt_in(),
+    Definition::new("bic", Category::Finance, bic_redactor),
+    Definition::new(
+        "account_number",
+        Category::Finance,
+        account_number_redactor,
+    ),
+    // Healthcare identifiers
+    Definition::new("mrn", Category::Identity, mrn_redactor),
+    Definition::new("npi", Category::Identity, npi_redactor).opt_in(),
+    Definition::new("dea", Category::Identity, dea_redactor).opt_in(),
+    // Regional identifiers, enabled by region
+    Definition::new("aadhaar", Category::Identity, aadhaar_redactor)
+        .region("in"),
+    Definition::new("pan", Category::Identity, pan_redactor).region("in"),
+    Definition::new("cpf", Category::Identity, cpf_redactor).region("br"),
+    Definition::new("cnpj", Category::Identity, cnpj_redactor).region("br"),
+    Definition::new("sin", Category::Identity, sin_redactor).region("ca"),
+    Definition::new("eu_vat", Category::Identity, eu_vat_redactor)
+        .region("eu"),
+    Definition::new("steuer_id", Category::Identity, steuer_id_redactor)
+        .region("de"),
+    Definition::new("nir", Category::Identity, nir_redactor).region("fr"),
+    Definition::new(
+        "routing_number",
+        Category::Finance,
+        routing_number_redactor,
+    )
+    .region("us"),
+    Definition::new(
+        "driver_license",
+        Category::Identity,
+        driver_license_redactor,
+    )
+    .configurable(configured_driver_license_redactor)
+    .region("us"),
+    Definition::new(
+        "license_plate",
+        Category::Identity,
+        license_plate_redactor,
+    )
+    .configurable(configured_license_plate_redactor)
+    .opt_in(),
+    // Generic detectors, after the specific ones
+    Definition::new("high_entropy", Category::Cloud, high_entropy_redactor)
+        .configurable(configured_high_entropy_redactor)
+        .severity(Severity::Secret)
+        .opt_in(),
+    Definition::new("hex_tokens", Category::Cloud, hex_tokens_redactor)
+        .configurable(configured_hex_tokens_redactor)
+        .severity(Severity::Secret)
+        .opt_in(),
+    Definition::new("base64_blobs", Category::Cloud, base64_blobs_redactor)
+        .configurable(configured_base64_blobs_redactor)
+        .severity(Severity::Secret)
+        .opt_in(),
+];
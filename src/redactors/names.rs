@@ -0,0 +1,72 @@
+use regex::Regex;
+
+use crate::redactor::Redactor;
+use crate::redactors::RedactorOptions;
+
+// The bundled wordlists of first and last names.
+const FIRST_NAMES: &str = include_str!("names/first.txt");
+const LAST_NAMES: &str = include_str!("names/last.txt");
+
+/// Redacts person names, by bundled wordlists of common first and last names,
+/// with `person`. Capitalized first names are redacted on their own, or with
+/// the last name following them (e.g. `Alice Smith`). Last names on their own
+/// are left alone, since many of them are also common words.
+pub fn person_names_redactor() -> Option<Redactor> {
+    configured_person_names_redactor(&RedactorOptions::default())
+}
+
+/// Creates a `Redactor` for person names, as configured by `options` (which
+/// may add `names` to the bundled first names).
+pub fn configured_person_names_redactor(
+    options: &RedactorOptions,
+) -> Option<Redactor> {
+    let first_names = words(FIRST_NAMES)
+        .chain(options.names.iter().map(String::as_str))
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join("|");
+    let last_names = words(LAST_NAMES)
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join("|");
+    let pattern = format!(
+        r"\b(?:{first_names})(?:[ \t]+(?:{last_names}))?\b",
+        first_names = first_names,
+        last_names = last_names,
+    );
+    Regex::new(&pattern)
+        .ok()
+        .map(|re| Redactor::regex(re, Some("person".to_string())))
+}
+
+// The words in a wordlist, skipping blank lines and comments.
+fn words(list: &str) -> impl Iterator<Item = &str> {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_person_names_redactor() {
+        let redactor = person_names_redactor().unwrap();
+        assert_eq!(
+            redactor.redact("Ticket from Alice Smith, cc Brian and Mr. Brown."),
+            "Ticket from person, cc person and Mr. Brown."
+        );
+        assert_eq!(redactor.redact("alice, Alicent"), "alice, Alicent");
+
+        let options = RedactorOptions {
+            names: vec!["Zaphod".to_string()],
+            ..RedactorOptions::default()
+        };
+        let redactor = configured_person_names_redactor(&options).unwrap();
+        assert_eq!(
+            redactor.redact("Zaphod Taylor and Alice"),
+            "person and person"
+        );
+    }
+}
@@ -0,0 +1,497 @@
+use regex::Regex;
+
+use crate::redactor::Redactor;
+use crate::redactors::RedactorOptions;
+use crate::redactors::patterns::is_luhn_valid;
+
+// The formats of the driver's license numbers of each US state (and DC).
+const DRIVER_LICENSE_FORMATS: [(&str, &str); 51] = [
+    ("AK", r"\d{1,7}"),
+    ("AL", r"\d{7,8}"),
+    ("AR", r"\d{4,9}"),
+    ("AZ", r"[A-Z]\d{8}|\d{9}"),
+    ("CA", r"[A-Z]\d{7}"),
+    ("CO", r"\d{9}|[A-Z]\d{3,6}|[A-Z]{2}\d{2,5}"),
+    ("CT", r"\d{9}"),
+    ("DC", r"\d{7}|\d{9}"),
+    ("DE", r"\d{1,7}"),
+    ("FL", r"[A-Z]\d{12}"),
+    ("GA", r"\d{7,9}"),
+    ("HI", r"[A-Z]\d{8}|\d{9}"),
+    ("IA", r"\d{9}|\d{3}[A-Z]{2}\d{4}"),
+    ("ID", r"[A-Z]{2}\d{6}[A-Z]|\d{9}"),
+    ("IL", r"[A-Z]\d{11,12}"),
+    ("IN", r"[A-Z]\d{9}|\d{9,10}"),
+    ("KS", r"[A-Z]\d[A-Z]\d[A-Z]|[A-Z]\d{8}|\d{9}"),
+    ("KY", r"[A-Z]\d{8,9}|\d{9}"),
+    ("LA", r"\d{1,9}"),
+    ("MA", r"[A-Z]\d{8}|\d{9}"),
+    ("MD", r"[A-Z]\d{12}"),
+    ("ME", r"\d{7,8}|\d{7}[A-Z]"),
+    ("MI", r"[A-Z]\d{10}|[A-Z]\d{12}"),
+    ("MN", r"[A-Z]\d{12}"),
+    ("MO", r"[A-Z]\d{5,9}|[A-Z]\d{6}R|\d{8}[A-Z]{2}|\d{9}[A-Z]?"),
+    ("MS", r"\d{9}"),
+    ("MT", r"[A-Z]\d[A-Z0-9]\d{2}[A-Z]{3}\d|\d{13}|\d{9}|[A-Z]\d{8}"),
+    ("NC", r"\d{1,12}"),
+    ("ND", r"[A-Z]{3}\d{6}|\d{9}"),
+    ("NE", r"[A-Z]\d{6,8}"),
+    ("NH", r"\d{2}[A-Z]{3}\d{5}"),
+    ("NJ", r"[A-Z]\d{14}"),
+    ("NM", r"\d{8,9}"),
+    ("NV", r"\d{9,10}|\d{12}|X\d{8}"),
+    ("NY", r"[A-Z]\d{7}|[A-Z]\d{18}|\d{8,9}|\d{16}"),
+    ("OH", r"[A-Z]\d{4,8}|[A-Z]{2}\d{3,7}|\d{8}"),
+    ("OK", r"[A-Z]\d{9}|\d{9}"),
+    ("OR", r"\d{1,9}|[A-Z]\d{6}|[A-Z]{2}\d{5}"),
+    ("PA", r"\d{8}"),
+    ("RI", r"\d{7}|[A-Z]\d{6}"),
+    ("SC", r"\d{5,11}"),
+    ("SD", r"\d{6,10}|\d{12}"),
+    ("TN", r"\d{7,9}"),
+    ("TX", r"\d{7,8}"),
+    ("UT", r"\d{4,10}"),
+    ("VA", r"[A-Z]\d{8,11}|\d{9}"),
+    ("VT", r"\d{8}|\d{7}A"),
+    ("WA", r"WDL[A-Z0-9]{9}|[A-Z*]{7}\d{3}[A-Z0-9]{2}"),
+    ("WI", r"[A-Z]\d{13}"),
+    ("WV", r"\d{7}|[A-Z]{1,2}\d{5,6}"),
+    ("WY", r"\d{9,10}"),
+];
+
+// The formats of the license plates of some countries, and of some US states
+// (as `us-` and the state). Formats which could match a prefix of others (like
+// the Swiss and Polish ones) go last, since the first matching format wins.
+const LICENSE_PLATE_FORMATS: [(&str, &str); 14] = [
+    ("at", r"[A-Z]{1,2}[ -]\d{1,5}[A-Z]{1,2}"),
+    ("de", r"[A-ZÄÖÜ]{1,3}-[A-Z]{1,2} ?\d{1,4}[EH]?"),
+    ("es", r"\d{4} ?[B-DF-HJ-NP-TV-Z]{3}"),
+    ("fr", r"[A-Z]{2}-\d{3}-[A-Z]{2}"),
+    ("gb", r"[A-Z]{2}\d{2} ?[A-Z]{3}"),
+    ("in", r"[A-Z]{2} ?\d{1,2} ?[A-Z]{1,3} ?\d{4}"),
+    ("it", r"[A-Z]{2} ?\d{3} ?[A-Z]{2}"),
+    ("nl", r"[A-Z]{2}-\d{3}-[A-Z]|\d-[A-Z]{3}-\d{2}|[A-Z]{2}-[A-Z]{3}-\d"),
+    ("us-ca", r"\d[A-Z]{3}\d{3}"),
+    ("us-fl", r"[A-Z]{3}[ -]?[A-Z]\d{2}|\d{3}[ -]?\d[A-Z]{2}"),
+    ("us-ny", r"[A-Z]{3}-?\d{4}"),
+    ("us-tx", r"[A-Z]{3}-?\d{4}|[A-Z]{2}\d-[A-Z]\d{3}"),
+    ("ch", r"[A-Z]{2} \d{1,6}"),
+    ("pl", r"[A-Z]{2,3} [A-Z0-9]{4,5}"),
+];
+
+// The multiplication table of the dihedral group D5, for the Verhoeff
+// checksum.
+const VERHOEFF_D: [[u8; 10]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+    [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+    [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+    [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+    [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+    [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+    [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+    [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+    [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+];
+
+// The permutations of the digits by their position, for the Verhoeff
+// checksum.
+const VERHOEFF_P: [[u8; 10]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+    [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+    [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+    [9, 4, 5, 3, 1, 2, 7, 8, 6, 0],
+    [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+    [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+    [7, 0, 4, 6, 9, 1, 5, 2, 8, 3],
+];
+
+/// Redacts Indian Aadhaar numbers (12 digits, e.g. `2341 2341 2346`), which
+/// pass the Verhoeff checksum.
+pub fn aadhaar_redactor() -> Option<Redactor> {
+    Regex::new(r"\b[2-9]\d{3}[ -]?\d{4}[ -]?\d{4}\b").ok().map(|re| {
+        Redactor::validated(
+            re,
+            is_verhoeff_valid,
+            Some("•••• •••• ••••".to_string()),
+        )
+    })
+}
+
+/// Redacts Indian PAN (Permanent Account Number) cards, e.g. `ABCPE1234F`.
+/// The fourth letter is the kind of holder, e.g. `P` for a person.
+pub fn pan_redactor() -> Option<Redactor> {
+    Regex::new(r"\b[A-Z]{3}[ABCFGHJLPT][A-Z]\d{4}[A-Z]\b")
+        .ok()
+        .map(|re| Redactor::regex(re, Some("••••••••••".to_string())))
+}
+
+/// Redacts Brazilian CPF numbers (individual taxpayer IDs), e.g.
+/// `529.982.247-25`, which have valid check digits.
+pub fn cpf_redactor() -> Option<Redactor> {
+    Regex::new(r"\b\d{3}\.?\d{3}\.?\d{3}-?\d{2}\b").ok().map(|re| {
+        Redactor::validated(
+            re,
+            |cpf| {
+                let digits = digits(cpf);
+                has_mod11_check_digits(&digits, &[10, 9, 8, 7, 6, 5, 4, 3, 2])
+            },
+            Some("•••.•••.•••-••".to_string()),
+        )
+    })
+}
+
+/// Redacts Brazilian CNPJ numbers (company taxpayer IDs), e.g.
+/// `11.222.333/0001-81`, which have valid check digits.
+pub fn cnpj_redactor() -> Option<Redactor> {
+    Regex::new(r"\b\d{2}\.?\d{3}\.?\d{3}/?\d{4}-?\d{2}\b").ok().map(|re| {
+        Redactor::validated(
+            re,
+            |cnpj| {
+                let digits = digits(cnpj);
+                has_mod11_check_digits(
+                    &digits,
+                    &[5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2],
+                )
+            },
+            Some("••.•••.•••/••••-••".to_string()),
+        )
+    })
+}
+
+/// Redacts Canadian SINs (Social Insurance Numbers), formatted like
+/// `046 454 286`, which pass the Luhn checksum.
+pub fn sin_redactor() -> Option<Redactor> {
+    Regex::new(r"\b\d{3}[ -]\d{3}[ -]\d{3}\b").ok().map(|re| {
+        Redactor::validated(re, is_luhn_valid, Some("••• ••• •••".to_string()))
+    })
+}
+
+/// Redacts EU VAT numbers, by the format of each member state after its
+/// prefix, e.g. `DE123456789` or `FR 12 345678901`. The prefix is kept.
+pub fn eu_vat_redactor() -> Option<Redactor> {
+    let formats = [
+        r"AT ?U\d{8}",
+        r"BE ?[01]\d{9}",
+        r"BG ?\d{9,10}",
+        r"CY ?\d{8}[A-Z]",
+        r"CZ ?\d{8,10}",
+        r"DE ?\d{9}",
+        r"DK ?\d{8}",
+        r"EE ?\d{9}",
+        r"EL ?\d{9}",
+        r"ES ?[A-Z0-9]\d{7}[A-Z0-9]",
+        r"FI ?\d{8}",
+        r"FR ?[A-HJ-NP-Z0-9]{2} ?\d{9}",
+        r"HR ?\d{11}",
+        r"HU ?\d{8}",
+        r"IE ?(?:\d{7}[A-W][A-I]?|\d[A-Z+*]\d{5}[A-W])",
+        r"IT ?\d{11}",
+        r"LT ?(?:\d{9}|\d{12})",
+        r"LU ?\d{8}",
+        r"LV ?\d{11}",
+        r"MT ?\d{8}",
+        r"NL ?\d{9}B\d{2}",
+        r"PL ?\d{10}",
+        r"PT ?\d{9}",
+        r"RO ?\d{2,10}",
+        r"SE ?\d{12}",
+        r"SI ?\d{8}",
+        r"SK ?\d{10}",
+    ];
+    let pattern = format!(r"\b(?:{})\b", formats.join("|"));
+    Regex::new(&pattern).ok().map(|re| {
+        Redactor::with_replacer(re, |vat| format!("{}••••••••", &vat[..2]))
+    })
+}
+
+/// Redacts German tax IDs (Steuerliche Identifikationsnummer), e.g.
+/// `86 095 742 719`, which have a valid (ISO 7064) check digit.
+pub fn steuer_id_redactor() -> Option<Redactor> {
+    Regex::new(r"\b[1-9]\d(?: ?\d{3}){3}\b").ok().map(|re| {
+        Redactor::validated(
+            re,
+            |id| {
+                let digits = digits(id);
+                let product = digits[..10].iter().fold(10, |product, d| {
+                    let sum = match (d + product) % 10 {
+                        0 => 10,
+                        sum => sum,
+                    };
+                    sum * 2 % 11
+                });
+                (11 - product) % 10 == digits[10]
+            },
+            Some("•• ••• ••• •••".to_string()),
+        )
+    })
+}
+
+/// Redacts French social security numbers (NIR), e.g.
+/// `1 85 05 78 006 084 91`, which have a valid key.
+pub fn nir_redactor() -> Option<Redactor> {
+    Regex::new(concat!(
+        r"\b[12378] ?\d{2} ?(?:0[1-9]|1[0-2]|[2-9]\d) ?(?:\d{2}|2[AB])",
+        r" ?\d{3} ?\d{3} ?\d{2}\b",
+    ))
+    .ok()
+    .map(|re| {
+        Redactor::validated(
+            re,
+            |nir| {
+                // Corsican departments (2A and 2B) count as 19 and 18.
+                let number = nir.replace(' ', "");
+                let (number, key) = number.split_at(13);
+                let number = number.replace("2A", "19").replace("2B", "18");
+                match (number.parse::<u64>(), key.parse::<u64>()) {
+                    (Ok(number), Ok(key)) => 97 - number % 97 == key,
+                    _ => false,
+                }
+            },
+            Some("• •• •• •• ••• ••• ••".to_string()),
+        )
+    })
+}
+
+/// Redacts US driver's license numbers after a keyword like
+/// `driver's license no:` or `DL #`, which is kept. The numbers follow the
+/// formats of any US state.
+pub fn driver_license_redactor() -> Option<Redactor> {
+    configured_driver_license_redactor(&RedactorOptions::default())
+}
+
+/// Creates a `Redactor` for US driver's license numbers, as configured by
+/// `options` (which may limit the formats to those of some `states`, e.g.
+/// `CA`).
+pub fn configured_driver_license_redactor(
+    options: &RedactorOptions,
+) -> Option<Redactor> {
+    let formats: Vec<&str> = DRIVER_LICENSE_FORMATS
+        .iter()
+        .filter(|(state, _)| {
+            options.states.is_empty()
+                || options.states.iter().any(|s| s.eq_ignore_ascii_case(state))
+        })
+        .map(|(_, format)| *format)
+        .collect();
+    if formats.is_empty() {
+        return None;
+    }
+    let pattern = format!(
+        concat!(
+            r"(?P<context>\b(?i:driver'?s?[ _-]?licen[cs]e|DL)",
+            r"(?i:[ _-]?(?:no\.?|number|num|id|#))?",
+            r#"["']?[ \t]*[:=#]?[ \t]*["']?)"#,
+            r"(?:{formats})\b",
+        ),
+        formats = formats.join("|"),
+    );
+    Regex::new(&pattern)
+        .ok()
+        .map(|re| Redactor::regex_with_capture(re, "${context}••••••••".into()))
+}
+
+/// Redacts vehicle license plates, by the formats of some countries (e.g.
+/// `AB12 CDE` in the UK) and US states (e.g. `7ABC123` in California).
+pub fn license_plate_redactor() -> Option<Redactor> {
+    configured_license_plate_redactor(&RedactorOptions::default())
+}
+
+/// Creates a `Redactor` for vehicle license plates, as configured by
+/// `options` (which may limit the formats to those of some `locales`, e.g.
+/// `gb` or `us-ca`).
+pub fn configured_license_plate_redactor(
+    options: &RedactorOptions,
+) -> Option<Redactor> {
+    let formats: Vec<&str> = LICENSE_PLATE_FORMATS
+        .iter()
+        .filter(|(locale, _)| {
+            options.locales.is_empty()
+                || options
+                    .locales
+                    .iter()
+                    .any(|l| l.eq_ignore_ascii_case(locale))
+        })
+        .map(|(_, format)| *format)
+        .collect();
+    if formats.is_empty() {
+        return None;
+    }
+    Regex::new(&format!(r"\b(?:{})\b", formats.join("|")))
+        .ok()
+        .map(|re| Redactor::regex(re, Some("•••••••".to_string())))
+}
+
+// The digits in `s`, ignoring any other characters, like separators.
+fn digits(s: &str) -> Vec<u32> {
+    s.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+// Whether the last two digits are the (Brazilian) modulo 11 check digits of
+// the others, with `weights` for the first of them. The weights for the second
+// one are the same, preceded by one more than the first weight.
+fn has_mod11_check_digits(digits: &[u32], weights: &[u32]) -> bool {
+    let len = weights.len();
+    // Repeated digits (e.g. `111.111.111-11`) pass, but aren't valid.
+    if digits.len() != len + 2 || digits.iter().all(|&d| d == digits[0]) {
+        return false;
+    }
+    let check_digit = |digits: &[u32], weights: &[u32]| {
+        let sum: u32 = digits.iter().zip(weights).map(|(d, w)| d * w).sum();
+        match sum % 11 {
+            0 | 1 => 0,
+            rest => 11 - rest,
+        }
+    };
+    let mut second = vec![weights[0] + 1];
+    second.extend(weights);
+    check_digit(&digits[..len], weights) == digits[len]
+        && check_digit(&digits[..=len], &second) == digits[len + 1]
+}
+
+// Whether the digits in `s` (ignoring any other characters, like separators)
+// pass the Verhoeff checksum used by Aadhaar numbers.
+fn is_verhoeff_valid(s: &str) -> bool {
+    let digits = digits(s).into_iter().rev();
+    let check = digits.enumerate().fold(0, |check, (i, digit)| {
+        VERHOEFF_D[check as usize][VERHOEFF_P[i % 8][digit as usize] as usize]
+    });
+    check == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aadhaar_redactor() {
+        let redactor = aadhaar_redactor().unwrap();
+        assert_eq!(
+            redactor.redact("Aadhaar: 2341 2341 2346"),
+            "Aadhaar: •••• •••• ••••"
+        );
+        assert_eq!(redactor.redact("234123412346"), "•••• •••• ••••");
+        // Fails the checksum, or starts with 0 or 1.
+        for text in ["2341 2341 2345", "1341 2341 2346"] {
+            assert_eq!(redactor.redact(text), text);
+        }
+    }
+
+    #[test]
+    fn test_cpf_redactor() {
+        let redactor = cpf_redactor().unwrap();
+        assert_eq!(redactor.redact("CPF 529.982.247-25"), "CPF •••.•••.•••-••");
+        assert_eq!(redactor.redact("52998224725"), "•••.•••.•••-••");
+        for text in ["529.982.247-24", "111.111.111-11"] {
+            assert_eq!(redactor.redact(text), text);
+        }
+    }
+
+    #[test]
+    fn test_cnpj_redactor() {
+        let redactor = cnpj_redactor().unwrap();
+        assert_eq!(
+            redactor.redact("CNPJ: 11.222.333/0001-81"),
+            "CNPJ: ••.•••.•••/••••-••"
+        );
+        assert_eq!(redactor.redact("11222333000181"), "••.•••.•••/••••-••");
+        assert_eq!(redactor.redact("11.222.333/0001-80"), "11.222.333/0001-80");
+    }
+
+    #[test]
+    fn test_sin_redactor() {
+        let redactor = sin_redactor().unwrap();
+        assert_eq!(redactor.redact("SIN: 046 454 286"), "SIN: ••• ••• •••");
+        assert_eq!(redactor.redact("130-692-544"), "••• ••• •••");
+        for text in ["130 692 545", "130692544"] {
+            assert_eq!(redactor.redact(text), text);
+        }
+    }
+
+    #[test]
+    fn test_eu_vat_redactor() {
+        let redactor = eu_vat_redactor().unwrap();
+        assert_eq!(
+            redactor.redact("VAT: DE123456789, NL 123456789B01"),
+            "VAT: DE••••••••, NL••••••••"
+        );
+        assert_eq!(redactor.redact("ATU12345678"), "AT••••••••");
+        for text in ["DE12345678", "US123456789"] {
+            assert_eq!(redactor.redact(text), text);
+        }
+    }
+
+    #[test]
+    fn test_steuer_id_redactor() {
+        let redactor = steuer_id_redactor().unwrap();
+        assert_eq!(redactor.redact("IdNr 86095742719"), "IdNr •• ••• ••• •••");
+        assert_eq!(redactor.redact("86 095 742 719"), "•• ••• ••• •••");
+        assert_eq!(redactor.redact("86095742718"), "86095742718");
+    }
+
+    #[test]
+    fn test_nir_redactor() {
+        let redactor = nir_redactor().unwrap();
+        assert_eq!(
+            redactor.redact("NIR: 1 85 05 78 006 084 91"),
+            "NIR: • •• •• •• ••• ••• ••"
+        );
+        assert_eq!(redactor.redact("185057800608491"), "• •• •• •• ••• ••• ••");
+        assert_eq!(redactor.redact("185057800608490"), "185057800608490");
+    }
+
+    #[test]
+    fn test_pan_redactor() {
+        let redactor = pan_redactor().unwrap();
+        assert_eq!(redactor.redact("PAN ABCPE1234F."), "PAN ••••••••••.");
+        assert_eq!(redactor.redact("ABCXE1234F"), "ABCXE1234F");
+    }
+
+    #[test]
+    fn test_driver_license_redactor() {
+        let redactor = driver_license_redactor().unwrap();
+        for (text, expected) in [
+            ("Driver's License: D1234567", "Driver's License: ••••••••"),
+            ("DL# 123456789.", "DL# ••••••••."),
+            (
+                "driver_license_number=S12345678901",
+                "driver_license_number=••••••••",
+            ),
+        ] {
+            assert_eq!(redactor.redact(text), expected);
+        }
+        for text in [
+            "D1234567",
+            "driver's license: expired",
+            "DL 1234567890123456789",
+        ] {
+            assert_eq!(redactor.redact(text), text);
+        }
+
+        let california = RedactorOptions {
+            states: vec!["ca".to_string()],
+            ..RedactorOptions::default()
+        };
+        let redactor = configured_driver_license_redactor(&california).unwrap();
+        assert_eq!(redactor.redact("DL: D1234567"), "DL: ••••••••");
+        assert_eq!(redactor.redact("DL: 123456789"), "DL: 123456789");
+    }
+
+    #[test]
+    fn test_license_plate_redactor() {
+        let redactor = license_plate_redactor().unwrap();
+        for text in ["AB12 CDE", "AB-123-CD", "7ABC123", "KA 01 AB 1234"] {
+            assert_eq!(redactor.redact(text), "•••••••");
+        }
+        assert_eq!(redactor.redact("Plate: M-AB 1234."), "Plate: •••••••.");
+        assert_eq!(redactor.redact("ab12 cde"), "ab12 cde");
+
+        let california = RedactorOptions {
+            locales: vec!["US-CA".to_string()],
+            ..RedactorOptions::default()
+        };
+        let redactor = configured_license_plate_redactor(&california).unwrap();
+        assert_eq!(redactor.redact("7ABC123 AB12 CDE"), "••••••• AB12 CDE");
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+use std::{
+    env,
+    fs,
+    io,
+};
+
+use regex::Regex;
+
+use crate::error::RedactorError;
+use crate::redactor::Redactor;
+
+/// The name of the secrets file.
+pub const SECRETS_FILE: &str = ".biip-secrets";
+
+/// Creates a `Redactor` for the values listed in `.biip-secrets` files.
+///
+/// The files are read from the current directory and the home directory. Each
+/// line of them is a literal value to redact, or a regex when prefixed with
+/// `regex:`. Blank lines and lines starting with `#` are ignored:
+///
+/// ```text
+/// # Literal values.
+/// my-hostname
+/// hunter2
+/// # Patterns.
+/// regex:ACME-\d{4}
+/// ```
+///
+/// Like secrets from the environment, the matched values are replaced with
+/// `••••⚿•`.
+///
+/// Returns `None` if there are no such files, or they list no values.
+pub fn secrets_file_redactor() -> Option<Redactor> {
+    try_secrets_file_redactor().ok().flatten()
+}
+
+/// Like [`secrets_file_redactor`], but fails if a file cannot be read or has
+/// an invalid regex.
+pub fn try_secrets_file_redactor() -> Result<Option<Redactor>, RedactorError>
+{
+    let mut contents = String::new();
+    for path in secrets_file_paths() {
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                contents.push_str(&text);
+                contents.push('\n');
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(RedactorError::Io(err)),
+        }
+    }
+    parse_secrets(&contents)
+}
+
+fn secrets_file_paths() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let dirs = [env::current_dir().ok(), env::home_dir()];
+    for path in dirs.into_iter().flatten().map(|d| d.join(SECRETS_FILE)) {
+        // Skip the same file twice, e.g. when run from the home directory.
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+// Builds a redactor for the values and patterns listed in `contents`.
+fn parse_secrets(contents: &str) -> Result<Option<Redactor>, RedactorError> {
+    let mut parts = Vec::new();
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.strip_prefix("regex:") {
+            Some(pattern) => {
+                // Compile each pattern on its own, so an invalid one can't
+                // change the meaning of the others.
+                Regex::new(pattern)?;
+                parts.push(format!("(?:{})", pattern));
+            }
+            None => parts.push(regex::escape(line)),
+        }
+    }
+
+    if parts.is_empty() {
+        return Ok(None);
+    }
+    let regex = Regex::new(&parts.join("|"))?;
+    Ok(Some(Redactor::regex(regex, Some(String::from("••••⚿•")))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_secrets() {
+        let redactor = parse_secrets(
+            "# Comment\n\nhunter2\n  my.host  \nregex:ACME-\\d{4}\n",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            redactor.redact("hunter2 at my.host, ACME-1234, myxhost"),
+            "••••⚿• at ••••⚿•, ••••⚿•, myxhost"
+        );
+
+        assert!(parse_secrets("# Nothing\n\n").unwrap().is_none());
+        assert!(matches!(
+            parse_secrets("regex:("),
+            Err(RedactorError::InvalidPattern(_))
+        ));
+    }
+}
@@ -1,43 +1,407 @@
-use std::env;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+use std::{
+    env,
+    fs,
+};
 
-use regex::RegexBuilder;
+use regex::{
+    Regex,
+    RegexBuilder,
+};
 
+use crate::error::RedactorError;
 use crate::redactor::Redactor;
+use crate::redactors::RedactorOptions;
 
 /// Creates a `Redactor` for the current user's username.
 ///
-/// This function reads the `USER` environment variable and creates a
-/// case-insensitive regex to replace occurrences of the username with `user`.
+/// This function reads the `USER` (or, on Windows, `USERNAME`) environment
+/// variable and creates a case-insensitive regex to replace occurrences of the
+/// username with `user`.
 ///
-/// Returns `None` if the `USER` environment variable is not set.
+/// Returns `None` if neither environment variable is set.
 pub fn username_redactor() -> Option<Redactor> {
-    match env::var("USER") {
-        Ok(user) => Some(Redactor::regex(
-            RegexBuilder::new(&format!(r"\b{}\b", regex::escape(&user)))
-                .case_insensitive(true)
-                .build()
-                .ok()?,
-            Some("user".to_string()),
-        )),
-        Err(_) => None,
-    }
+    try_username_redactor().ok().flatten()
+}
+
+/// Like [`username_redactor`], but fails if neither the `USER` nor the
+/// `USERNAME` environment variable is set.
+pub fn try_username_redactor() -> Result<Option<Redactor>, RedactorError> {
+    let user = env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .map_err(|_| RedactorError::MissingEnv("USER".to_string()))?;
+    let regex = RegexBuilder::new(&format!(r"\b{}\b", regex::escape(&user)))
+        .case_insensitive(true)
+        .build()?;
+    Ok(Some(Redactor::regex(regex, Some("user".to_string()))))
 }
 
 /// Creates a `Redactor` for the user's home directory.
 ///
-/// This function gets the user's home directory path and creates a `Redactor`
-/// to replace it with `~`.
+/// This function gets the user's home directory path (and, on Windows,
+/// `USERPROFILE`) and creates a `Redactor` to replace it with `~`. Windows
+/// paths are also replaced with forward slashes (e.g. `C:/Users/me`), escaped
+/// backslashes (e.g. `C:\\Users\\me`, as in JSON) and a lowercase drive
+/// letter.
 ///
 /// Returns `None` if the home directory path cannot be determined.
 pub fn home_redactor() -> Option<Redactor> {
-    match env::home_dir() {
-        Some(path) => path
-            .into_os_string()
-            .into_string()
-            .map(|path_str| Redactor::simple(path_str, Some("~".to_string())))
-            .ok(),
-        None => None,
+    try_home_redactor().ok().flatten()
+}
+
+/// Like [`home_redactor`], but fails if the home directory path cannot be
+/// determined.
+pub fn try_home_redactor() -> Result<Option<Redactor>, RedactorError> {
+    let path = env::home_dir()
+        .ok_or_else(|| RedactorError::MissingEnv("HOME".to_string()))?;
+    let path_str = path
+        .into_os_string()
+        .into_string()
+        .map_err(|_| RedactorError::Unavailable)?;
+    let mut paths = home_paths(&path_str);
+    if let Ok(profile) = env::var("USERPROFILE") {
+        paths.extend(home_paths(&profile));
     }
+    Redactor::literals(paths, Some("~".to_string())).map(Some)
+}
+
+// The ways a home directory may be written: as is, and for Windows paths
+// (like `C:\Users\me`), with any kind of separator and drive letter case.
+fn home_paths(home: &str) -> Vec<String> {
+    let drive = home
+        .strip_prefix(|c: char| c.is_ascii_alphabetic())
+        .is_some_and(|rest| rest.starts_with(':'));
+    if !drive && !home.contains('\\') {
+        return vec![home.to_string()];
+    }
+    let parts: Vec<&str> = home.split(['\\', '/']).collect();
+    let mut paths = Vec::new();
+    for separator in ["\\", "/", "\\\\"] {
+        let path = parts.join(separator);
+        if drive {
+            paths.push(path[..1].to_lowercase() + &path[1..]);
+            paths.push(path[..1].to_uppercase() + &path[1..]);
+        } else {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Creates a `Redactor` for the home directories of any users, like
+/// `/home/alice`, `/Users/alice` or `C:\Users\alice`, replacing them with
+/// `~other`. The current user's home directory is redacted by
+/// [`home_redactor`] instead.
+pub fn other_homes_redactor() -> Option<Redactor> {
+    configured_other_homes_redactor(&RedactorOptions::default())
+}
+
+/// Creates a `Redactor` for the home directories of any users, as configured
+/// by `options` (which may limit them to the `known_users` in `/etc/passwd`).
+pub fn configured_other_homes_redactor(
+    options: &RedactorOptions,
+) -> Option<Redactor> {
+    let known_users = options.known_users.then(|| {
+        let passwd = fs::read_to_string("/etc/passwd").unwrap_or_default();
+        passwd_users(&passwd).map(str::to_string).collect()
+    });
+    other_homes(known_users)
+}
+
+// Redacts the home directories of any users, or only of the `known_users`.
+fn other_homes(known_users: Option<Vec<String>>) -> Option<Redactor> {
+    // Shared directories which don't belong to a user.
+    const SHARED: [&str; 4] = ["Shared", "Public", "Default", "All Users"];
+    Regex::new(r"(?:\b[A-Za-z]:[\\/]Users[\\/]|/home/|/Users/)\w(?:[\w.-]*\w)?")
+        .ok()
+        .map(|re| {
+            Redactor::validated_with_replacer(
+                re,
+                move |home| {
+                    let name = home.rsplit(['/', '\\']).next().unwrap_or(home);
+                    !SHARED.contains(&name)
+                        && known_users
+                            .as_ref()
+                            .is_none_or(|users| users.iter().any(|u| u == name))
+                },
+                |_| "~other".to_string(),
+            )
+        })
+}
+
+/// Creates a `Redactor` for the machine's hostname.
+///
+/// This function reads the hostname from the kernel and `/etc/hostname`, or,
+/// only if neither is available (e.g. on macOS or Windows), from the
+/// `hostname` command, and creates a case-insensitive regex to replace
+/// occurrences of it (and of its first label, for fully qualified names like
+/// `box.example.com`) with `host`.
+///
+/// Returns `None` if the hostname can't be determined, or is `localhost`.
+pub fn hostname_redactor() -> Option<Redactor> {
+    let mut hostnames: Vec<String> =
+        ["/proc/sys/kernel/hostname", "/etc/hostname"]
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .collect();
+    if hostnames.is_empty() {
+        hostnames.push(command_output("hostname", &[]));
+    }
+    let mut names: Vec<String> = hostnames
+        .iter()
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty() && name != "localhost")
+        .flat_map(|name| {
+            let short = name.split('.').next().unwrap_or_default().to_string();
+            [name, short]
+        })
+        .collect();
+    names.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    names.dedup();
+    if names.is_empty() {
+        return None;
+    }
+    let names: Vec<String> =
+        names.iter().map(|name| regex::escape(name)).collect();
+    RegexBuilder::new(&format!(r"\b(?:{})\b", names.join("|")))
+        .case_insensitive(true)
+        .build()
+        .ok()
+        .map(|re| Redactor::regex(re, Some("host".to_string())))
+}
+
+/// Creates a `Redactor` for the names (SSIDs) of the Wi-Fi networks known to
+/// this machine, read with `nmcli` (NetworkManager) or `networksetup` (macOS),
+/// replacing them with `wifi`.
+///
+/// Returns `None` if there are no known networks.
+pub fn wifi_ssids_redactor() -> Option<Redactor> {
+    configured_wifi_ssids_redactor(&RedactorOptions::default())
+}
+
+/// Creates a `Redactor` for the names (SSIDs) of Wi-Fi networks, as
+/// configured by `options` (which may add `ssids` to the known networks).
+pub fn configured_wifi_ssids_redactor(
+    options: &RedactorOptions,
+) -> Option<Redactor> {
+    let mut ssids = known_ssids();
+    ssids.extend(options.ssids.iter().cloned());
+    literals_regex(ssids).map(|re| Redactor::regex(re, Some("wifi".into())))
+}
+
+/// Creates a `Redactor` for the values of the environment variables which
+/// identify the user or machine, replacing them with a placeholder for each:
+/// `LOGNAME` with `user`, `EMAIL` with `•••@•••`, `NAME` with `name`,
+/// `HOSTNAME` with `host` and `MAIL` (the mailbox path) with `mail`.
+///
+/// Returns `None` if none of them is set.
+pub fn identity_env_redactor() -> Option<Redactor> {
+    let placeholders: HashMap<String, &str> = [
+        ("LOGNAME", "user"),
+        ("EMAIL", "•••@•••"),
+        ("NAME", "name"),
+        ("HOSTNAME", "host"),
+        ("MAIL", "mail"),
+    ]
+    .into_iter()
+    .filter_map(|(name, placeholder)| {
+        let value = env::var(name).ok()?;
+        Some((value.trim().to_string(), placeholder))
+    })
+    .collect();
+    let regex = literals_regex(placeholders.keys().cloned().collect())?;
+    Some(Redactor::with_replacer(regex, move |value| {
+        placeholders.get(value).unwrap_or(&"•••").to_string()
+    }))
+}
+
+/// Creates a `Redactor` for the user's git identity: the `user.name` and
+/// `user.email` in their global git config (`~/.gitconfig` or
+/// `~/.config/git/config`, read without running `git`), and the `GIT_AUTHOR_*`
+/// and `GIT_COMMITTER_*` environment variables. Names are replaced with
+/// `user`, and emails with `•••@•••`.
+///
+/// Returns `None` if there is no git identity.
+pub fn git_identity_redactor() -> Option<Redactor> {
+    let home = env::home_dir();
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+    let configs: Vec<String> = [
+        home.map(|home| home.join(".gitconfig")),
+        config_home.map(|config| config.join("git").join("config")),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|path| fs::read_to_string(path).ok())
+    .collect();
+    let configured = configs
+        .iter()
+        .flat_map(|config| git_config_identity(config))
+        .map(str::to_string);
+    let from_env = [
+        "GIT_AUTHOR_NAME",
+        "GIT_AUTHOR_EMAIL",
+        "GIT_COMMITTER_NAME",
+        "GIT_COMMITTER_EMAIL",
+    ]
+    .iter()
+    .filter_map(|name| env::var(name).ok());
+    literals_regex(configured.chain(from_env).collect()).map(|re| {
+        Redactor::with_replacer(re, |identity| {
+            if identity.contains('@') {
+                "•••@•••".to_string()
+            } else {
+                "user".to_string()
+            }
+        })
+    })
+}
+
+/// Creates a `Redactor` for the hosts the user connects to with SSH: the
+/// aliases and hostnames in `~/.ssh/config`, and the (unhashed) hosts in
+/// `~/.ssh/known_hosts`, replacing them with `ssh-host`.
+///
+/// Returns `None` if there are no such hosts.
+pub fn ssh_hosts_redactor() -> Option<Redactor> {
+    let ssh = env::home_dir()?.join(".ssh");
+    let config = fs::read_to_string(ssh.join("config")).unwrap_or_default();
+    let known_hosts =
+        fs::read_to_string(ssh.join("known_hosts")).unwrap_or_default();
+    let hosts = ssh_config_hosts(&config)
+        .chain(known_hosts_hosts(&known_hosts))
+        .map(str::to_string)
+        .collect();
+    literals_regex(hosts).map(|re| Redactor::regex(re, Some("ssh-host".into())))
+}
+
+// The user names in a passwd file.
+fn passwd_users(passwd: &str) -> impl Iterator<Item = &str> {
+    passwd
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split(':').next())
+}
+
+// The `name` and `email` in the `[user]` section of a git config.
+fn git_config_identity(config: &str) -> impl Iterator<Item = &str> {
+    config
+        .lines()
+        .map(|line| line.split(['#', ';']).next().unwrap_or_default().trim())
+        .scan(false, |in_user, line| {
+            if let Some(section) = line.strip_prefix('[') {
+                let section = section.trim_end_matches(']').trim();
+                *in_user = section.eq_ignore_ascii_case("user");
+                return Some(None);
+            }
+            let value = line
+                .split_once('=')
+                .filter(|(key, _)| {
+                    let key = key.trim().to_lowercase();
+                    *in_user && (key == "name" || key == "email")
+                })
+                .map(|(_, value)| value.trim().trim_matches('"'));
+            Some(value)
+        })
+        .flatten()
+}
+
+// The host aliases (except patterns) and hostnames in an SSH config.
+fn ssh_config_hosts(config: &str) -> impl Iterator<Item = &str> {
+    config
+        .lines()
+        .filter_map(|line| {
+            let (keyword, values) = line.trim().split_once([' ', '\t', '='])?;
+            let keyword = keyword.to_lowercase();
+            (keyword == "host" || keyword == "hostname").then_some(values)
+        })
+        .flat_map(str::split_whitespace)
+        .filter(|host| !host.contains(['*', '?', '!', '%']))
+}
+
+// The hosts in a `known_hosts` file, without their ports (e.g. `[host]:2222`),
+// skipping hashed ones.
+fn known_hosts_hosts(known_hosts: &str) -> impl Iterator<Item = &str> {
+    known_hosts
+        .lines()
+        .filter(|line| !line.starts_with(['#', '|', '@']))
+        .filter_map(|line| line.split_whitespace().next())
+        .flat_map(|hosts| hosts.split(','))
+        .map(|host| match host.strip_prefix('[') {
+            Some(host) => host.split_once("]:").map_or(host, |(host, _)| host),
+            None => host,
+        })
+}
+
+// A regex for any of `values`, preferring the longest (e.g. `Home 5G` over
+// `Home`), which only matches whole words where they start or end with word
+// characters. Returns `None` if there are no (non-blank) values.
+fn literals_regex(mut values: Vec<String>) -> Option<Regex> {
+    values = values
+        .into_iter()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect();
+    values.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    values.dedup();
+    if values.is_empty() {
+        return None;
+    }
+    let boundary = |c: Option<char>| match c {
+        Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+        _ => "",
+    };
+    let pattern = values
+        .iter()
+        .map(|value| {
+            format!(
+                "{}{}{}",
+                boundary(value.chars().next()),
+                regex::escape(value),
+                boundary(value.chars().last()),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("|");
+    Regex::new(&pattern).ok()
+}
+
+// The SSIDs of the Wi-Fi networks known to NetworkManager (by the names of
+// its connections) or to macOS.
+fn known_ssids() -> Vec<String> {
+    let nmcli = command_output(
+        "nmcli",
+        &["-t", "-f", "TYPE,NAME", "connection", "show"],
+    );
+    let networksetup = command_output(
+        "networksetup",
+        &["-listpreferredwirelessnetworks", "en0"],
+    );
+    let connections = nmcli.lines().filter_map(|line| {
+        // Terse output escapes colons and backslashes in values.
+        let name = line.strip_prefix("802-11-wireless:")?;
+        Some(name.replace(r"\:", ":").replace(r"\\", r"\"))
+    });
+    let preferred = networksetup
+        .lines()
+        .filter_map(|line| line.strip_prefix('\t'))
+        .map(str::to_string);
+    connections.chain(preferred).collect()
+}
+
+// The standard output of a command, or nothing if it is not available or
+// fails.
+fn command_output(program: &str, args: &[&str]) -> String {
+    Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -65,4 +429,127 @@ mod tests {
             "My home directory is: ~"
         );
     }
+
+    #[test]
+    fn test_home_paths() {
+        assert_eq!(home_paths("/home/me"), ["/home/me"]);
+        let redactor =
+            Redactor::literals(home_paths(r"C:\Users\me"), Some("~".into()))
+                .unwrap();
+        for (path, expected) in [
+            (r"C:\Users\me\Desktop", r"~\Desktop"),
+            (r"c:\Users\me\Desktop", r"~\Desktop"),
+            ("C:/Users/me/Desktop", "~/Desktop"),
+            (r"C:\\Users\\me\\Desktop", r"~\\Desktop"),
+        ] {
+            assert_eq!(redactor.redact(path), expected);
+        }
+    }
+
+    #[test]
+    fn test_other_homes_redactor() {
+        let redactor = other_homes_redactor().unwrap();
+        for (text, expected) in [
+            ("cd /home/alice/logs", "cd ~other/logs"),
+            ("/Users/bob.smith.", "~other."),
+            (r"C:\Users\carol\Desktop", r"~other\Desktop"),
+            ("/Users/Shared/x", "/Users/Shared/x"),
+        ] {
+            assert_eq!(redactor.redact(text), expected);
+        }
+
+        let passwd = "# users\nroot:x:0:0:root:/root:/bin/bash\n\n\
+                      alice:x:1000:1000::/home/alice:/bin/sh\n";
+        let users = passwd_users(passwd).map(str::to_string).collect();
+        let redactor = other_homes(Some(users)).unwrap();
+        assert_eq!(
+            redactor.redact("/home/alice /home/root /home/bob"),
+            "~other ~other /home/bob"
+        );
+    }
+
+    #[test]
+    fn test_hostname_redactor() {
+        let Some(redactor) = hostname_redactor() else {
+            return;
+        };
+        let hostname = command_output("hostname", &[]);
+        let hostname = hostname.trim();
+        if !hostname.is_empty() && hostname != "localhost" {
+            assert_eq!(
+                redactor.redact(&format!("[me@{hostname} ~]$ ls")),
+                "[me@host ~]$ ls"
+            );
+        }
+        assert_eq!(redactor.redact("localhost"), "localhost");
+    }
+
+    #[test]
+    fn test_identity_env_redactor() {
+        unsafe {
+            env::set_var("NAME", "Ford Prefect");
+            env::set_var("EMAIL", "ford@guide.galaxy");
+        }
+        let redactor = identity_env_redactor().unwrap();
+        assert_eq!(
+            redactor.redact("From: Ford Prefect <ford@guide.galaxy>"),
+            "From: name <•••@•••>"
+        );
+    }
+
+    #[test]
+    fn test_ssh_hosts() {
+        let config = "Host bastion prod-*\n  HostName bastion.example.com\n\
+                      Host *\n  User me\nhostname=10.0.0.7";
+        assert_eq!(
+            ssh_config_hosts(config).collect::<Vec<_>>(),
+            ["bastion", "bastion.example.com", "10.0.0.7"]
+        );
+        let known_hosts = "gitlab.example.com,10.0.0.8 ssh-ed25519 AAAA\n\
+                           [git.example.com]:2222 ssh-rsa AAAA\n\
+                           |1|hashed= ssh-rsa AAAA";
+        assert_eq!(
+            known_hosts_hosts(known_hosts).collect::<Vec<_>>(),
+            ["gitlab.example.com", "10.0.0.8", "git.example.com"]
+        );
+    }
+
+    #[test]
+    fn test_git_identity_redactor() {
+        unsafe {
+            env::set_var("GIT_AUTHOR_NAME", "Zaphod Beeblebrox");
+            env::set_var("GIT_AUTHOR_EMAIL", "zaphod@heart-of-gold.space");
+        }
+        let redactor = git_identity_redactor().unwrap();
+        assert_eq!(
+            redactor.redact(
+                "Author: Zaphod Beeblebrox <zaphod@heart-of-gold.space>"
+            ),
+            "Author: user <•••@•••>"
+        );
+    }
+
+    #[test]
+    fn test_git_config_identity() {
+        let config = "[core]\n\tname = not-me\n[User]\n\
+                      \tname = \"Arthur Dent\" # full name\n\
+                      \temail=arthur@example.com\n[alias]\n\temail = log";
+        assert_eq!(
+            git_config_identity(config).collect::<Vec<_>>(),
+            ["Arthur Dent", "arthur@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_wifi_ssids_redactor() {
+        let options = RedactorOptions {
+            ssids: vec!["Home".to_string(), "Home 5G".to_string()],
+            ..RedactorOptions::default()
+        };
+        let redactor = configured_wifi_ssids_redactor(&options).unwrap();
+        assert_eq!(
+            redactor.redact("Connected to Home 5G, not Home or Homebrew"),
+            "Connected to wifi, not wifi or Homebrew"
+        );
+    }
 }
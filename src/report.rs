@@ -0,0 +1,71 @@
+//! Reports of the redactions made by [`Biip`](crate::Biip).
+use std::collections::BTreeMap;
+
+/// A single redaction made to a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redaction {
+    /// The name of the redactor which made the redaction.
+    pub redactor: String,
+    /// The byte offset in the redacted text where the replacement starts.
+    pub start: usize,
+    /// The byte offset in the redacted text where the replacement ends
+    /// (exclusive).
+    pub end: usize,
+}
+
+/// Sensitive text detected by [`Biip::scan`](crate::Biip::scan), without
+/// redacting it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detection {
+    /// The name of the redactor which detected the text.
+    pub redactor: String,
+    /// The byte offset in the scanned text where the detection starts.
+    pub start: usize,
+    /// The byte offset in the scanned text where the detection ends
+    /// (exclusive).
+    pub end: usize,
+}
+
+impl Detection {
+    /// Returns the length, in bytes, of the detected text.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns true if the detected text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// A summary of what was redacted from a text, without the redacted values
+/// themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// The redactions, ordered by their position in the redacted text.
+    pub redactions: Vec<Redaction>,
+}
+
+impl Report {
+    /// Returns the total number of redactions.
+    pub fn total(&self) -> usize {
+        self.redactions.len()
+    }
+
+    /// Returns the number of redactions made by the named redactor.
+    pub fn count(&self, redactor: &str) -> usize {
+        self.redactions
+            .iter()
+            .filter(|r| r.redactor == redactor)
+            .count()
+    }
+
+    /// Returns the number of redactions made by each redactor.
+    pub fn counts(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for r in &self.redactions {
+            *counts.entry(r.redactor.as_str()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
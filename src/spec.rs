@@ -0,0 +1,157 @@
+//! Declarative redactor definitions.
+//!
+//! A [`RedactorSpec`] describes a regex-based redactor as plain data, so that
+//! sets of redactors can be kept in configuration. With the `serde` feature,
+//! specs can be (de)serialized, e.g. from JSON, TOML or YAML.
+#[cfg(feature = "serde")]
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::error::RedactorError;
+use crate::redactor::{
+    PatternOptions,
+    Redactor,
+};
+use crate::redactors::network::{
+    is_public_ipv4,
+    is_public_ipv6,
+};
+use crate::redactors::patterns::is_luhn_valid;
+
+/// A regex-based redactor, described as data.
+///
+/// # Example
+///
+/// ```
+/// use biip::{
+///     Biip,
+///     RedactorSpec,
+/// };
+///
+/// let spec = RedactorSpec {
+///     name: "ticket".to_string(),
+///     pattern: r"acme-\d+".to_string(),
+///     case_insensitive: true,
+///     ..Default::default()
+/// };
+/// let biip = Biip::builder()
+///     .without_defaults()
+///     .with_named(&spec.name, Some(spec.build().unwrap()))
+///     .build();
+/// assert_eq!(biip.process("see ACME-42"), "see •••");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct RedactorSpec {
+    /// The name of the redactor, e.g. for reports or to remove it by name.
+    pub name: String,
+    /// The regex pattern to search for.
+    pub pattern: String,
+    /// The replacement string. If `None`, a default replacer will be used.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub replacement: Option<String>,
+    /// Whether the pattern matches case-insensitively.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub case_insensitive: bool,
+    /// A check matches must pass to be redacted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub validator: Option<ValidatorKind>,
+}
+
+/// The built-in checks a [`RedactorSpec`] can apply to its matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ValidatorKind {
+    /// Only redact IPv4 addresses which are public.
+    PublicIpv4,
+    /// Only redact IPv6 addresses which are public.
+    PublicIpv6,
+    /// Only redact numbers passing the Luhn checksum, e.g. payment cards.
+    Luhn,
+}
+
+impl ValidatorKind {
+    /// Whether `text` passes the check.
+    pub fn validate(self, text: &str) -> bool {
+        match self {
+            ValidatorKind::PublicIpv4 => is_public_ipv4(text),
+            ValidatorKind::PublicIpv6 => is_public_ipv6(text),
+            ValidatorKind::Luhn => is_luhn_valid(text),
+        }
+    }
+}
+
+impl RedactorSpec {
+    /// Builds the `Redactor` described, failing if the pattern is invalid.
+    pub fn build(&self) -> Result<Redactor, RedactorError> {
+        let options = PatternOptions {
+            beep: self.replacement.clone(),
+            case_insensitive: self.case_insensitive,
+            ..Default::default()
+        };
+        let redactor = Redactor::from_pattern(&self.pattern, options)?;
+        match (self.validator, redactor) {
+            (Some(kind), Redactor::Re(regex, beep)) => Ok(Redactor::validated(
+                regex,
+                move |text| kind.validate(text),
+                Some(beep),
+            )),
+            (_, redactor) => Ok(redactor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_with_validator() {
+        let spec = RedactorSpec {
+            name: "card".to_string(),
+            pattern: r"\b\d{4}(?: ?\d{4}){3}\b".to_string(),
+            replacement: Some("[card]".to_string()),
+            validator: Some(ValidatorKind::Luhn),
+            ..Default::default()
+        };
+        let redactor = spec.build().unwrap();
+        assert_eq!(
+            redactor.redact("4111 1111 1111 1111, 4111 1111 1111 1112"),
+            "[card], 4111 1111 1111 1112"
+        );
+    }
+
+    #[test]
+    fn test_build_invalid_pattern() {
+        let spec = RedactorSpec {
+            pattern: "(".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(spec.build(), Err(RedactorError::InvalidPattern(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize() {
+        let specs: Vec<RedactorSpec> = serde_json::from_str(
+            r#"[
+                {"name": "ip", "pattern": "\\d+\\.\\d+\\.\\d+\\.\\d+",
+                 "validator": "public_ipv4"},
+                {"name": "ticket", "pattern": "acme-\\d+",
+                 "replacement": "[ticket]", "case_insensitive": true}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(specs[0].validator, Some(ValidatorKind::PublicIpv4));
+        assert_eq!(specs[1].replacement.as_deref(), Some("[ticket]"));
+
+        let ip = specs[0].build().unwrap();
+        assert_eq!(ip.redact("8.8.8.8 10.0.0.1"), "••• 10.0.0.1");
+        let ticket = specs[1].build().unwrap();
+        assert_eq!(ticket.redact("ACME-1"), "[ticket]");
+    }
+}
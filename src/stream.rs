@@ -0,0 +1,260 @@
+//! Redaction of text which arrives in arbitrary chunks, e.g. from a pipe.
+use crate::biip::Biip;
+use crate::redactor::apply_into;
+
+/// Redacts a stream of text fed to it in chunks, catching matches which span
+/// the boundaries between chunks (or lines).
+///
+/// Text is only released a line at a time, and the tail of the text seen so
+/// far (the "window") is carried over until more text has arrived, or until
+/// [`StreamRedactor::finish`]. A match which straddles the edge of what is
+/// released, or which runs to the end of the text seen so far (e.g. a private
+/// key block which hasn't ended yet), is carried over in full, so that it can
+/// be extended by the next chunk.
+///
+/// Matches spanning lines, which are longer than the window, may still be
+/// split. So the window should comfortably exceed the longest multi-line
+/// value to be redacted. With a window of `0`, text is released as soon as a
+/// line is complete, which suits interactive streams (e.g. `tail -f`).
+///
+/// What is carried over is bounded, though: once it exceeds the window by
+/// [`StreamRedactor::MAX_LINE`] bytes (e.g. a line which never ends, or a
+/// match which keeps growing), it is released regardless, even mid-line.
+///
+/// ```
+/// use biip::Biip;
+/// use biip::redactors::email_redactor;
+/// use biip::stream::StreamRedactor;
+///
+/// let biip = Biip::builder()
+///     .without_defaults()
+///     .with(email_redactor())
+///     .build();
+/// let mut stream = StreamRedactor::new(biip);
+/// let mut out = String::new();
+/// stream.push("mail foo@", &mut out);
+/// stream.push("bar.com\n", &mut out);
+/// stream.finish(&mut out);
+/// assert_eq!(out, "mail •••@•••\n");
+/// ```
+pub struct StreamRedactor {
+    biip: Biip,
+    window: usize,
+    // Text which has been received, but not yet released.
+    carry: String,
+    // Trailing bytes of an incomplete UTF-8 sequence, for `push_bytes`.
+    partial: Vec<u8>,
+}
+
+impl StreamRedactor {
+    /// The default size of the window, in bytes.
+    pub const DEFAULT_WINDOW: usize = 4096;
+
+    /// The number of bytes past the window which may be carried over, before
+    /// text is released regardless of lines and matches.
+    pub const MAX_LINE: usize = 64 * 1024;
+
+    /// Creates a new `StreamRedactor` with the default window size.
+    pub fn new(biip: Biip) -> Self {
+        StreamRedactor::with_window(biip, StreamRedactor::DEFAULT_WINDOW)
+    }
+
+    /// Creates a new `StreamRedactor`, carrying over (at least) `window` bytes
+    /// between chunks.
+    pub fn with_window(biip: Biip, window: usize) -> Self {
+        StreamRedactor {
+            biip,
+            window,
+            carry: String::new(),
+            partial: Vec::new(),
+        }
+    }
+
+    /// Feeds a chunk of text, appending any text which is ready to `out`,
+    /// redacted.
+    pub fn push(&mut self, chunk: &str, out: &mut String) {
+        self.carry.push_str(chunk);
+        let len = self.carry.len();
+        if len <= self.window {
+            return;
+        }
+
+        // Past the cap, release the text outside the window even if it isn't
+        // a complete line.
+        let forced = len > self.window + StreamRedactor::MAX_LINE;
+
+        // Release up to the last complete line outside the window.
+        let outside = &self.carry.as_bytes()[..len - self.window];
+        let cut = match outside.iter().rposition(|&b| b == b'\n') {
+            Some(newline) => newline + 1,
+            None if forced => self.carry.floor_char_boundary(len - self.window),
+            None => return,
+        };
+        // Carry over any match straddling the cut (or which may not be
+        // complete yet), along with what follows. Past the cap, such a match
+        // at the start of the carried over text is released as it is.
+        let matches = self.biip.matches(&self.carry, None);
+        let straddling = matches
+            .iter()
+            .find(|m| m.start < cut && (m.end > cut || m.end == len));
+        let cut = match straddling {
+            Some(m) if forced && m.start == 0 => m.end,
+            Some(m) => m.start,
+            None => cut,
+        };
+
+        let ready: Vec<_> =
+            matches.into_iter().filter(|m| m.end <= cut).collect();
+        apply_into(&self.carry[..cut], &ready, out);
+        self.carry.drain(..cut);
+    }
+
+    /// Feeds a chunk of bytes, like [`StreamRedactor::push`]. A UTF-8
+    /// sequence split across chunks is reassembled, while invalid UTF-8 is
+    /// replaced with `U+FFFD`.
+    pub fn push_bytes(&mut self, chunk: &[u8], out: &mut String) {
+        self.partial.extend_from_slice(chunk);
+        let bytes = std::mem::take(&mut self.partial);
+
+        let mut text = String::with_capacity(bytes.len());
+        let mut rest = &bytes[..];
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    text.push_str(valid);
+                    break;
+                }
+                Err(err) => {
+                    let (valid, invalid) = rest.split_at(err.valid_up_to());
+                    // Only the bytes up to `valid_up_to` are valid UTF-8.
+                    text.push_str(std::str::from_utf8(valid).unwrap());
+                    match err.error_len() {
+                        Some(len) => {
+                            text.push(char::REPLACEMENT_CHARACTER);
+                            rest = &invalid[len..];
+                        }
+                        // An incomplete sequence at the end of the chunk.
+                        None => {
+                            self.partial = invalid.to_vec();
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        self.push(&text, out);
+    }
+
+    /// Redacts and appends all the remaining text to `out`.
+    pub fn finish(&mut self, out: &mut String) {
+        if !self.partial.is_empty() {
+            let partial = std::mem::take(&mut self.partial);
+            self.carry.push_str(&String::from_utf8_lossy(&partial));
+        }
+        self.biip.process_into(&self.carry, out);
+        self.carry.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use super::*;
+    use crate::redactor::Redactor;
+    use crate::redactors;
+
+    fn email_stream(window: usize) -> StreamRedactor {
+        let biip = Biip::builder()
+            .without_defaults()
+            .with(redactors::email_redactor())
+            .build();
+        StreamRedactor::with_window(biip, window)
+    }
+
+    #[test]
+    fn test_match_spanning_chunks() {
+        let mut stream = email_stream(8);
+        let mut out = String::new();
+
+        stream.push("one\nmail foo@ba", &mut out);
+        assert_eq!(out, "one\n");
+        stream.push("r.com and a@b.io\n", &mut out);
+        assert!(!out.contains("foo@"));
+        stream.finish(&mut out);
+        assert_eq!(out, "one\nmail •••@••• and •••@•••\n");
+    }
+
+    #[test]
+    fn test_block_spanning_chunks() {
+        let biip = Biip::builder()
+            .without_defaults()
+            .with_custom(
+                Redactor::block("BEGIN", "END", Some("[block]".to_string()))
+                    .unwrap(),
+            )
+            .build();
+        let mut stream = StreamRedactor::with_window(biip, 32);
+        let mut out = String::new();
+
+        stream.push("text\nBEGIN\nsecret\n", &mut out);
+        assert_eq!(out, "");
+        stream.push("more secret\nEND\nplain\n", &mut out);
+        stream.finish(&mut out);
+        assert_eq!(out, "text\n[block]\nplain\n");
+    }
+
+    #[test]
+    fn test_line_without_end_is_released() {
+        let mut stream = email_stream(8);
+        let mut out = String::new();
+        let line = "é".repeat(1000);
+        let mut pushed = 0;
+        while pushed <= StreamRedactor::MAX_LINE + 8 {
+            stream.push(&line, &mut out);
+            pushed += line.len();
+        }
+        assert!(!out.is_empty());
+        stream.finish(&mut out);
+        assert_eq!(out, line.repeat(pushed / line.len()));
+    }
+
+    #[test]
+    fn test_unterminated_match_is_released() {
+        let biip = Biip::builder()
+            .without_defaults()
+            .with_custom(Redactor::regex(
+                Regex::new(r"(?s)BEGIN.*").unwrap(),
+                Some("[block]".to_string()),
+            ))
+            .build();
+        let mut stream = StreamRedactor::with_window(biip, 0);
+        let mut out = String::new();
+
+        stream.push("text\nBEGIN\n", &mut out);
+        assert_eq!(out, "text\n");
+        let line = format!("{}\n", "secret ".repeat(100));
+        while out == "text\n" {
+            stream.push(&line, &mut out);
+            let cap = StreamRedactor::MAX_LINE + line.len();
+            assert!(stream.carry.len() <= cap);
+        }
+        assert_eq!(out, "text\n[block]");
+        stream.push("plain\n", &mut out);
+        stream.finish(&mut out);
+        assert_eq!(out, "text\n[block]plain\n");
+    }
+
+    #[test]
+    fn test_push_bytes_reassembles_utf8() {
+        let mut stream = email_stream(8);
+        let mut out = String::new();
+        let text = "héllo wörld, mail a@b.io";
+        for byte in text.as_bytes() {
+            stream.push_bytes(&[*byte], &mut out);
+        }
+        stream.push_bytes(b"\xFF", &mut out);
+        stream.finish(&mut out);
+        assert_eq!(out, "héllo wörld, mail •••@•••\u{FFFD}");
+    }
+}
@@ -0,0 +1,384 @@
+//! Replacement styles, which control how redacted values are shown.
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{
+    BuildHasher,
+    RandomState,
+};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use hmac::{
+    Hmac,
+    Mac,
+};
+use regex::Regex;
+use sha2::Sha256;
+
+#[cfg(feature = "serde")]
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::redactors::MARKER_ICONS;
+
+
+// Matches the labels of `Style::Labels` and `Style::Numbered`, e.g. `[EMAIL]`
+// or `[EMAIL-2]`.
+static LABEL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[[A-Z][A-Z0-9_]*(?:-\d+)?\]").unwrap());
+
+// Matches the pseudonyms of `Style::Pseudonyms`, e.g. `email:3fa2c1`.
+static PSEUDONYM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[a-z][a-z0-9_]*:[0-9a-f]{6}\b").unwrap());
+
+// The key for pseudonyms when none is given, random for each process.
+static PROCESS_KEY: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    (0..4)
+        .flat_map(|_| RandomState::new().hash_one(0).to_le_bytes())
+        .collect()
+});
+
+/// Returns the key for pseudonyms when none is given, which is random for each
+/// process.
+pub(crate) fn process_key() -> &'static [u8] {
+    &PROCESS_KEY
+}
+
+// Labels for the built-in redactors, where they differ from the (uppercased)
+// name of the redactor.
+const LABELS: &[(&str, &str)] = &[
+    ("username", "USER"),
+    ("other_homes", "HOME"),
+    ("hostname", "HOST"),
+    ("git_identity", "USER"),
+    ("identity_env", "IDENTITY"),
+    ("wifi_ssids", "WIFI"),
+    ("ssh_hosts", "SSH_HOST"),
+    ("secrets", "SECRET"),
+    ("secrets_file", "SECRET"),
+    ("credential_files", "SECRET"),
+    ("high_entropy", "SECRET"),
+    ("hex_tokens", "HEX_TOKEN"),
+    ("base64_blobs", "BASE64_BLOB"),
+    ("custom_patterns", "CUSTOM"),
+    ("url_credentials", "CREDENTIALS"),
+    ("mac_address", "MAC"),
+    ("cloud_keys", "API_KEY"),
+    ("gitlab_tokens", "GITLAB_TOKEN"),
+    ("stripe_keys", "STRIPE_KEY"),
+    ("bot_tokens", "BOT_TOKEN"),
+    ("paas_tokens", "PAAS_TOKEN"),
+    ("private_keys", "PRIVATE_KEY"),
+    ("pgp_blocks", "PGP_BLOCK"),
+    ("certificates", "CERTIFICATE"),
+    ("kubernetes_secrets", "KUBERNETES_SECRET"),
+    ("terraform_secrets", "TERRAFORM_SECRET"),
+    ("person_names", "PERSON"),
+    ("phone_number", "PHONE"),
+    ("dea", "DEA_NUMBER"),
+    ("eu_vat", "VAT_NUMBER"),
+    ("steuer_id", "TAX_ID"),
+];
+
+/// How redacted values are shown, applied consistently across all redactors.
+///
+/// # Example
+///
+/// ```
+/// use biip::Biip;
+/// use biip::redactors::email_redactor;
+/// use biip::style::Style;
+///
+/// let biip = Biip::builder()
+///     .without_defaults()
+///     .with(email_redactor())
+///     .with_style(Style::Ascii)
+///     .build();
+/// assert_eq!(biip.process("Mail foo@bar.com"), "Mail ***@***");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Style {
+    /// The redactors' own replacements, e.g. `•••@•••` or `••••⚿•`.
+    #[default]
+    Bullets,
+    /// Like [`Style::Bullets`], but in plain ASCII: the bullets and icons are
+    /// replaced with `*`, e.g. `***@***`.
+    Ascii,
+    /// Like [`Style::Bullets`], but with the bullets and icons replaced with
+    /// the given character.
+    Mask(char),
+    /// A bracketed label of the kind of value redacted, e.g. `[EMAIL]` or
+    /// `[SECRET]`, derived from the name of the redactor.
+    Labels,
+    /// Like [`Style::Labels`], but numbered so that each distinct value gets
+    /// its own placeholder, e.g. `[EMAIL-1]` and `[EMAIL-2]`. The numbers are
+    /// stable for the lifetime of a [`Biip`](crate::Biip) instance (and its
+    /// clones), so repeated values can still be correlated.
+    Numbered,
+    /// The given character repeated for each character of the redacted value,
+    /// e.g. `********`, so that column alignment and offsets are kept. Line
+    /// breaks within the value are kept too.
+    PreserveLength(char),
+    /// A short token derived from the value with a keyed HMAC, e.g.
+    /// `email:3fa2c1`. With the same key (see
+    /// [`BiipBuilder::with_key`](crate::BiipBuilder::with_key)), a value
+    /// always maps to the same token, across files and runs, so redacted data
+    /// can still be joined on it. Without a key, a random one is used for
+    /// each process.
+    Pseudonyms,
+}
+
+impl Style {
+    /// Applies the style to the replacement for a match of `value` by the
+    /// named redactor.
+    pub(crate) fn apply(
+        self,
+        name: &str,
+        value: &str,
+        replacement: &str,
+    ) -> String {
+        let mask = match self {
+            Style::Bullets => return replacement.to_string(),
+            Style::Labels | Style::Numbered | Style::Pseudonyms => {
+                return format!("[{}]", label(name));
+            }
+            Style::PreserveLength(mask) => {
+                return value
+                    .chars()
+                    .map(|c| if c == '\n' || c == '\r' { c } else { mask })
+                    .collect();
+            }
+            Style::Ascii => '*',
+            Style::Mask(mask) => mask,
+        };
+        replacement
+            .chars()
+            // Drop the variation selector of emoji icons (e.g. `☁️`), so each
+            // icon becomes a single mask character.
+            .filter(|&c| c != '\u{FE0F}')
+            .map(|c| {
+                if c == '•' || MARKER_ICONS.contains(&c) {
+                    mask
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+
+    /// Finds the spans of replacement markers already in `string` (runs of
+    /// `•` and the icons within them, and labels for `Style::Labels`), so that
+    /// processing redacted text again leaves it untouched.
+    pub(crate) fn markers(self, string: &str) -> Vec<(usize, usize)> {
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        let mut run: Option<(usize, bool)> = None;
+        for (i, c) in string.char_indices().chain([(string.len(), ' ')]) {
+            if c == '•' || MARKER_ICONS.contains(&c) {
+                let (start, has_dot) = run.unwrap_or((i, false));
+                run = Some((start, has_dot || c == '•'));
+            } else if let Some((start, has_dot)) = run.take()
+                && has_dot
+            {
+                spans.push((start, i));
+            }
+        }
+
+        if matches!(self, Style::Labels | Style::Numbered) {
+            // Labels contain no bullets, so never overlap the runs above.
+            spans.extend(LABEL.find_iter(string).map(|m| (m.start(), m.end())));
+            spans.sort_unstable();
+        }
+        if self == Style::Pseudonyms {
+            let pseudonyms = PSEUDONYM.find_iter(string);
+            spans.extend(pseudonyms.map(|m| (m.start(), m.end())));
+            spans.sort_unstable();
+        }
+        spans
+    }
+}
+
+/// Returns the pseudonym for a value redacted by the named redactor, e.g.
+/// `email:3fa2c1`, keyed with `key` (or the key of the process).
+pub(crate) fn pseudonym(key: Option<&[u8]>, name: &str, value: &str) -> String {
+    let key = key.unwrap_or(process_key());
+    let mut mac = Hmac::<Sha256>::new_from_slice(key)
+        .expect("HMAC accepts keys of any length");
+    mac.update(value.as_bytes());
+    let digest = mac.finalize().into_bytes();
+    let token: String =
+        digest[..3].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}:{}", label(name).to_lowercase(), token)
+}
+
+/// The numbers assigned to the values redacted with `Style::Numbered`.
+#[derive(Debug, Default)]
+pub(crate) struct Numbering {
+    // The numbers of the values, by label.
+    numbers: HashMap<String, HashMap<String, usize>>,
+}
+
+impl Numbering {
+    /// Returns the numbered placeholder for a value redacted by the named
+    /// redactor, e.g. `[EMAIL-1]`.
+    pub(crate) fn placeholder(&mut self, name: &str, value: &str) -> String {
+        let label = label(name);
+        let numbers = self.numbers.entry(label.clone()).or_default();
+        let next = numbers.len() + 1;
+        let number = *numbers.entry(value.to_string()).or_insert(next);
+        format!("[{}-{}]", label, number)
+    }
+}
+
+// The label for a redactor, e.g. `EMAIL` for `email`.
+fn label(name: &str) -> String {
+    match LABELS.iter().find(|(n, _)| *n == name) {
+        Some((_, label)) => label.to_string(),
+        None => name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect(),
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Style::Bullets => write!(f, "bullets"),
+            Style::Ascii => write!(f, "ascii"),
+            Style::Mask(mask) => write!(f, "mask:{}", mask),
+            Style::Labels => write!(f, "labels"),
+            Style::Numbered => write!(f, "numbered"),
+            Style::Pseudonyms => write!(f, "pseudonyms"),
+            Style::PreserveLength(mask) => {
+                write!(f, "preserve-length:{}", mask)
+            }
+        }
+    }
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    /// Parses a style: `bullets`, `ascii`, `labels`, `numbered`,
+    /// `pseudonyms`, `mask:C`
+    /// for a mask character `C`, or `preserve-length` (optionally with a mask
+    /// character, as `preserve-length:C`).
+    fn from_str(s: &str) -> Result<Style, String> {
+        let (name, mask) = match s.split_once(':') {
+            Some((name, mask)) => {
+                let mut chars = mask.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(mask), None) => (name, Some(mask)),
+                    _ => return Err(format!("invalid mask in style '{}'", s)),
+                }
+            }
+            None => (s, None),
+        };
+        match (name, mask) {
+            ("bullets", None) => Ok(Style::Bullets),
+            ("ascii", None) => Ok(Style::Ascii),
+            ("labels", None) => Ok(Style::Labels),
+            ("numbered", None) => Ok(Style::Numbered),
+            ("pseudonyms", None) => Ok(Style::Pseudonyms),
+            ("mask", Some(mask)) => Ok(Style::Mask(mask)),
+            ("preserve-length", mask) => {
+                Ok(Style::PreserveLength(mask.unwrap_or('*')))
+            }
+            _ => Err(format!("unknown style '{}'", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let apply = |style: Style, bullets| style.apply("x", "", bullets);
+        assert_eq!(apply(Style::Bullets, "••••⚿•"), "••••⚿•");
+        assert_eq!(apply(Style::Ascii, "••••⚿•"), "******");
+        assert_eq!(apply(Style::Ascii, "••••☁️•"), "******");
+        assert_eq!(apply(Style::Ascii, "••••🦊•"), "******");
+        assert_eq!(apply(Style::Ascii, "••••💳•"), "******");
+        assert_eq!(apply(Style::Ascii, "••••📜•"), "******");
+        assert_eq!(apply(Style::Mask('#'), "(•••) •••-••••"), "(###) ###-####");
+        assert_eq!(apply(Style::Ascii, "[email]"), "[email]");
+
+        let style = Style::PreserveLength('*');
+        assert_eq!(style.apply("email", "é@b.io", "•••@•••"), "******");
+        assert_eq!(style.apply("pem", "ab\ncd", "•••"), "**\n**");
+    }
+
+    #[test]
+    fn test_marker_icons() {
+        for icon in MARKER_ICONS.iter().filter(|&&c| c != '\u{FE0F}') {
+            let marker = format!("••••{icon}\u{FE0F}•");
+            assert_eq!(Style::Ascii.apply("x", "", &marker), "******");
+            assert_eq!(Style::Bullets.markers(&marker), [(0, marker.len())]);
+        }
+    }
+
+    #[test]
+    fn test_labels() {
+        let label = |name| Style::Labels.apply(name, "", "•••");
+        assert_eq!(label("email"), "[EMAIL]");
+        assert_eq!(label("ipv4"), "[IPV4]");
+        assert_eq!(label("secrets"), "[SECRET]");
+        assert_eq!(label("my-ticket"), "[MY_TICKET]");
+    }
+
+    #[test]
+    fn test_numbering() {
+        let mut numbering = Numbering::default();
+        assert_eq!(numbering.placeholder("email", "a@b.io"), "[EMAIL-1]");
+        assert_eq!(numbering.placeholder("email", "c@d.io"), "[EMAIL-2]");
+        assert_eq!(numbering.placeholder("ipv4", "8.8.8.8"), "[IPV4-1]");
+        assert_eq!(numbering.placeholder("email", "a@b.io"), "[EMAIL-1]");
+    }
+
+    #[test]
+    fn test_pseudonym() {
+        let key = Some(&b"secret"[..]);
+        let token = pseudonym(key, "email", "a@b.io");
+        assert!(PSEUDONYM.is_match(&token), "{}", token);
+        assert!(token.starts_with("email:"));
+        assert_eq!(pseudonym(key, "email", "a@b.io"), token);
+        assert_ne!(pseudonym(key, "email", "c@d.io"), token);
+        assert_ne!(pseudonym(Some(b"other"), "email", "a@b.io"), token);
+        assert!(pseudonym(None, "secrets", "x").starts_with("secret:"));
+    }
+
+    #[test]
+    fn test_markers() {
+        let text = "[EMAIL] •••@••• [note]";
+        assert_eq!(Style::Bullets.markers(text), [(8, 17), (18, 27)]);
+        assert_eq!(Style::Labels.markers(text), [(0, 7), (8, 17), (18, 27)]);
+        assert_eq!(Style::Numbered.markers("[IPV4-12]"), [(0, 9)]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let styles = [Style::Bullets, Style::Ascii, Style::Mask('x')];
+        let more = [Style::Labels, Style::Numbered, Style::PreserveLength('-')];
+        let styles = styles.into_iter().chain(more).chain([Style::Pseudonyms]);
+        for style in styles {
+            assert_eq!(style.to_string().parse::<Style>(), Ok(style));
+        }
+        assert_eq!("preserve-length".parse(), Ok(Style::PreserveLength('*')));
+        assert!("mask:".parse::<Style>().is_err());
+        assert!("ascii:x".parse::<Style>().is_err());
+        assert!("mask:ab".parse::<Style>().is_err());
+        assert!("fancy".parse::<Style>().is_err());
+    }
+}